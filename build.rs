@@ -0,0 +1,126 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This build script generates the regression tests of the benchmarks. For
+//! each folder `tests/resources/<Benchmark>` having a matching reference file
+//! `sol_<benchmark>.txt` (the name of the folder in snake case) at the root 
+//! of the crate, it emits one test per instance listed in that file. These 
+//! tests are included by `tests/benchmarks.rs`.
+//!
+//! Besides the regular lines of a reference file (see the `compare` module), 
+//! the following directives are understood:
+//! * `#! width <w>` sets the width multiplier used to solve the instances
+//! * `#! ignore <instance> ...` marks the tests of these instances as ignored
+//!   (typically because they take too long to solve).
+//! * `#! exact <instance> <value>` gives the exact optimal value of the 
+//!   instance when the value listed in the file is rounded. The test of that
+//!   instance then checks that the solver finds exactly that value.
+
+use std::{collections::HashMap, env, fmt::Write as _, fs, path::Path};
+
+fn main() {
+    let root      = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let root      = Path::new(&root);
+    let resources = root.join("tests/resources");
+    println!("cargo:rerun-if-changed={}", resources.display());
+
+    let mut folders = fs::read_dir(&resources).unwrap()
+        .filter_map(Result::ok)
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    folders.sort();
+
+    let mut code = String::new();
+    for bench in folders {
+        let reference = root.join(format!("sol_{}.txt", snake_case(&bench)));
+        println!("cargo:rerun-if-changed={}", reference.display());
+        if let Ok(text) = fs::read_to_string(&reference) {
+            generate(&mut code, &bench, &text);
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("benchmarks.rs");
+    fs::write(out, code).unwrap();
+}
+
+/// Emits a module holding one test per instance of the reference file
+fn generate(code: &mut String, bench: &str, reference: &str) {
+    let mut width   = "None".to_string();
+    let mut ignored = vec![];
+    let mut exact   = HashMap::new();
+    let mut tests   = vec![];
+    for line in reference.lines() {
+        if let Some(directive) = line.strip_prefix("#!") {
+            let mut tokens = directive.split_whitespace();
+            match tokens.next() {
+                Some("width")  => width = format!("Some({})", tokens.next().unwrap()),
+                Some("ignore") => ignored.extend(tokens.map(str::to_string)),
+                Some("exact")  => match (tokens.next(), tokens.next()) {
+                    (Some(instance), Some(value)) => { exact.insert(instance.to_string(), value.to_string()); },
+                    _ => panic!("malformed directive '{}' in the reference file of {}", line, bench)
+                },
+                _ => panic!("unknown directive '{}' in the reference file of {}", line, bench)
+            }
+            continue;
+        }
+        let data   = line.split('#').next().unwrap();
+        let mut tk = data.split_whitespace();
+        if let (Some(instance), Some(value)) = (tk.next(), tk.next()) {
+            tests.push((instance.to_string(), value.to_string()));
+        }
+    }
+
+    writeln!(code, "mod {} {{", snake_case(bench)).unwrap();
+    writeln!(code, "    use super::check;").unwrap();
+    for (instance, value) in tests {
+        let ignore = if ignored.contains(&instance) { " #[ignore]" } else { "" };
+        writeln!(code, "    #[test]{}", ignore).unwrap();
+        writeln!(code, "    fn {}() {{", identifier(&instance)).unwrap();
+        let exact  = exact.get(&instance).map_or("None".to_string(), |v| format!("Some(\"{}\")", v));
+        writeln!(code, "        check(\"{}/{}\", \"{}\", {}, {});", bench, instance, value, exact, width).unwrap();
+        writeln!(code, "    }}").unwrap();
+    }
+    writeln!(code, "}}").unwrap();
+}
+/// Converts a CamelCase name to snake_case (acronyms are kept together)
+fn snake_case(name: &str) -> String {
+    let mut out  = String::new();
+    let mut prev = None;
+    for c in name.chars() {
+        if c.is_uppercase() && prev.is_some_and(char::is_lowercase) {
+            out.push('_');
+        }
+        out.extend(c.to_lowercase());
+        prev = Some(c);
+    }
+    out
+}
+/// Turns the name of an instance file into the name of a test function
+fn identifier(instance: &str) -> String {
+    let name = instance.to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
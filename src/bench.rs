@@ -0,0 +1,111 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the building blocks of the `bench` command: finding
+//! the instances of a benchmark, naming them, and keeping track of the
+//! results which were already written to the output file so that an
+//! interrupted campaign can be resumed.
+
+use std::{collections::HashMap, fs::{self, File, OpenOptions}, io::{self, BufRead, BufReader, Write}, path::{Path, PathBuf}};
+
+use crate::{instance::TSPTWInstance, report::{read_records, Format, Record}, tour::Tour};
+
+/// Lists the instance files designated by the given pattern, sorted by path.
+/// The pattern can either be a directory (in which case all the files it
+/// holds, recursively, are considered to be instances) or a glob pattern
+/// (for instance `tests/resources/*/rbg*.tw`).
+pub fn instances(pattern: &str) -> Result<Vec<PathBuf>, glob::PatternError> {
+    let pattern = if Path::new(pattern).is_dir() {
+        format!("{}/**/*", glob::Pattern::escape(pattern.trim_end_matches('/')))
+    } else {
+        pattern.to_string()
+    };
+    let mut files = glob::glob(&pattern)?
+        .filter_map(Result::ok)
+        .filter(|p| p.is_file())
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+/// The name under which an instance is reported: the name of the folder
+/// holding the instance (the benchmark) followed by the name of the file.
+pub fn instance_name<P: AsRef<Path>>(fname: P) -> String {
+    let fname = fname.as_ref();
+    let name  = fname.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let bench = fname.parent().and_then(Path::file_name).map(|n| n.to_string_lossy()).unwrap_or_default();
+
+    format!("{}/{}", bench, name)
+}
+
+/// The file in which the records of a benchmark campaign are written. Each
+/// record is written as soon as it is available so that a crash does not
+/// lose the results which were obtained so far.
+pub struct Output {
+    file  : File,
+    format: Format,
+    /// The tour of each instance which has a record (if one was found)
+    done  : HashMap<String, Option<Vec<usize>>>,
+}
+impl Output {
+    /// Opens the given output file. When the file already exists, the
+    /// instances it holds a record for are considered done and the new
+    /// records are appended to it. Otherwise, the file is created and the
+    /// header of the format (if any) is written first. 
+    ///
+    /// A crash may have left the last line half written: that line is 
+    /// dropped so that the next record starts on a line of its own.
+    pub fn open<P: AsRef<Path>>(path: P, format: Format) -> io::Result<Self> {
+        let path     = path.as_ref();
+        let mut done = HashMap::new();
+        if path.exists() {
+            let content  = fs::read(path)?;
+            let complete = content.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+            if complete < content.len() {
+                OpenOptions::new().write(true).open(path)?.set_len(complete as u64)?;
+            }
+            let records = read_records(BufReader::new(&content[..complete]).lines(), format)?;
+            done.extend(records.into_iter().map(|r| (r.instance, r.tour)));
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if file.metadata()?.len() == 0 {
+            if let Some(header) = format.header() {
+                writeln!(file, "{}", header)?;
+            }
+        }
+        Ok(Self { file, format, done })
+    }
+    /// Returns true iff the output already holds a record for that instance
+    /// (`inst` is the instance itself). A record whose tour does not fit the
+    /// instance (it does not visit the right nodes) does not count.
+    pub fn is_done(&self, instance: &str, inst: &TSPTWInstance) -> bool {
+        match self.done.get(instance) {
+            None              => false,
+            Some(None)        => true,
+            Some(Some(tour))  => Tour::from_sequence(inst, tour).is_ok(),
+        }
+    }
+    /// Appends the given record to the output file
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        writeln!(self.file, "{}", record.render(self.format))?;
+        self.file.flush()?;
+        self.done.insert(record.instance.clone(), record.tour.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a solution checker for the TSPTW. It follows the
+//! logic of the `check_solution` utility written by Lopez-Ibanez and Blum
+//! for their Beam-ACO paper: a solution is a permutation of the customers
+//! (the depot is implicitly visited first and last) and it is evaluated by
+//! simulating the tour, waiting whenever a time window is not open yet.
+
+use std::{error, fmt, io::{self, BufRead, Lines}};
+
+use crate::{instance::TSPTWInstance, report::{Format, Record}, schedule::{schedule, Stop}, tour::{Tour, TourError}};
+
+/// This enumeration lists the reasons why a permutation cannot be evaluated
+#[derive(Debug)]
+pub enum CheckError {
+    /// The underlying reader failed
+    Io(io::Error),
+    /// Some token of the solution is not a node id
+    NotANumber(String),
+    /// The solution file does not hold any solution
+    NoSolution,
+    /// The solution is not a valid tour
+    InvalidTour(TourError),
+}
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckError::Io(e) =>
+                write!(f, "i/o error: {}", e),
+            CheckError::NotANumber(token) =>
+                write!(f, "'{}' is not a node", token),
+            CheckError::NoSolution =>
+                write!(f, "no solution found"),
+            CheckError::InvalidTour(e) =>
+                write!(f, "invalid tour: {}", e),
+        }
+    }
+}
+impl error::Error for CheckError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            CheckError::Io(e)          => Some(e),
+            CheckError::InvalidTour(e) => Some(e),
+            _                          => None
+        }
+    }
+}
+impl From<io::Error> for CheckError {
+    fn from(e: io::Error) -> Self {
+        CheckError::Io(e)
+    }
+}
+impl From<TourError> for CheckError {
+    fn from(e: TourError) -> Self {
+        CheckError::InvalidTour(e)
+    }
+}
+
+/// This is the outcome of the evaluation of some tour
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    /// The evaluated tour
+    pub tour      : Tour,
+    /// The total time spent travelling along the tour
+    pub tour_cost : usize,
+    /// The time at which the salesman is back at the depot (when there are
+    /// several vehicles, this is the total duration of their routes). When
+    /// the tour ends elsewhere, this is the time at which the salesman
+    /// reaches the end node or is done with the last customer.
+    pub makespan  : usize,
+    /// The timing of each stop along the route
+    pub stops     : Vec<Stop>,
+    /// For each stop, the difference between the start of service and the
+    /// closing of the time window (positive when the window was missed)
+    pub delays    : Vec<isize>,
+    /// The nodes whose time window was violated (a node which may be served
+    /// late only violates its window when it is served after its deadline)
+    pub violations: Vec<usize>,
+    /// The total penalty incurred for the late services
+    pub penalty   : usize,
+    /// The precedences (before, after) of the instance which were violated
+    pub inversions: Vec<(usize, usize)>,
+    /// The load of the vehicle when it leaves each stop
+    pub loads     : Vec<isize>,
+    /// The nodes after which the load of the vehicle exceeded its capacity
+    /// (or was negative)
+    pub overloads : Vec<usize>,
+    /// The sum of the prizes of the optional customers which were left out
+    pub forfeited : usize,
+    /// The weighted sum of the times at which the service of the customers
+    /// starts
+    pub latency   : usize,
+}
+impl Evaluation {
+    /// Returns true iff no time window, no precedence and no capacity 
+    /// constraint was violated
+    pub fn is_feasible(&self) -> bool {
+        self.violations.is_empty() && self.inversions.is_empty() && self.overloads.is_empty()
+    }
+}
+
+/// Returns the total time spent travelling along the route. Each vehicle 
+/// leaves the depot when its shift starts and any other node as soon as it
+/// is done with the service (the schedule tells when that is).
+fn travel_cost(inst: &TSPTWInstance, route: &[usize], stops: &[Stop]) -> usize {
+    let mut vehicle = 0;
+    let mut cost    = 0;
+    for k in 1..route.len() {
+        let leave = if k > 1 && route[k - 1] == 0 {
+            vehicle += 1;
+            inst.shift(vehicle).earliest + inst.service_times[0]
+        } else {
+            stops[k - 1].departure
+        };
+        cost += inst.travel_time(route[k - 1], route[k], leave);
+    }
+    cost
+}
+
+/// Evaluates the given tour
+pub fn evaluate(inst: &TSPTWInstance, tour: &Tour) -> Evaluation {
+    let route      = tour.route();
+    let stops      = schedule(inst, route);
+    let tour_cost  = travel_cost(inst, route, &stops);
+    let delays     = stops.iter()
+        .map(|s| s.start as isize - inst.timewindows[s.node].latest as isize)
+        .collect::<Vec<_>>();
+
+    // Each vehicle is on the road from the start of its shift until it is 
+    // back at the depot, which must happen before its shift ends. The 
+    // vehicles which go straight back to the depot are not used.
+    let mut makespan   = 0;
+    let mut violations = vec![];
+    let mut penalty    = 0;
+    let mut vehicle    = 0;
+    for (k, s) in stops.iter().enumerate().skip(1) {
+        if s.node == 0 && stops[k - 1].node == 0 {
+            vehicle += 1;
+            continue;
+        }
+        let shift    = inst.shift(vehicle);
+        let deadline = if s.node == 0 { inst.deadline(0).min(shift.latest) } else { inst.deadline(s.node) };
+        if s.start > deadline {
+            violations.push(s.node);
+        }
+        penalty += inst.lateness_cost(s.node, s.start);
+        if s.node == 0 || k + 1 == stops.len() {
+            let finish = if tour.is_open() { s.departure } else { s.start };
+            makespan += finish - shift.earliest;
+            vehicle  += 1;
+        }
+    }
+
+    let mut rank   = vec![0; inst.nb_nodes as usize];
+    for (k, node) in tour.customers().iter().enumerate() {
+        rank[*node] = k;
+    }
+    let inversions = inst.precedences.iter()
+        .filter(|(before, after)| rank[*before] > rank[*after])
+        .copied()
+        .collect();
+
+    // Each vehicle leaves the depot with the initial load and that load is
+    // left untouched when the last vehicle reaches the end of the tour
+    let initial    = inst.loads[0].max(0);
+    let mut loads  = vec![initial];
+    for (k, node) in route.iter().enumerate().skip(1) {
+        let load = loads[k - 1];
+        loads.push(match *node {
+            0 if k + 1 < route.len()      => initial,
+            _ if !inst.is_customer(*node) => load,
+            _                             => load + inst.loads[*node],
+        });
+    }
+    let capacity   = inst.capacity.map_or(isize::MAX, |c| c as isize);
+    let overloads  = route.iter().zip(loads.iter())
+        .filter(|(_, l)| inst.capacity.is_some() && (**l < 0 || **l > capacity))
+        .map(|(n, _)| *n)
+        .collect();
+
+    let forfeited  = inst.customers()
+        .filter(|i| !route.contains(i))
+        .map(|i| inst.prize(i))
+        .sum();
+
+    let latency    = stops.iter()
+        .filter(|s| inst.is_customer(s.node))
+        .map(|s| inst.weights[s.node] * s.start)
+        .sum();
+
+    Evaluation{tour: tour.clone(), tour_cost, makespan, stops, delays, violations, penalty, inversions, loads, overloads, forfeited, latency}
+}
+/// Reads the tour from the given lines (see `read_permutation`) and 
+/// evaluates it
+pub fn check<B: BufRead>(inst: &TSPTWInstance, lines: Lines<B>) -> Result<Evaluation, CheckError> {
+    let perm = read_permutation(lines)?;
+    let tour = Tour::from_sequence(inst, &perm)?;
+    Ok(evaluate(inst, &tour))
+}
+
+/// Reads a solution from the given lines. These lines can either be the
+/// output of the `solve` command in any format (in which case, the 
+/// permutation is taken from the first record) or simply hold the 
+/// permutation itself.
+pub fn read_permutation<B: BufRead>(lines: Lines<B>) -> Result<Vec<usize>, CheckError> {
+    let formats = [Format::Json, Format::Csv, Format::Table];
+    let mut bare = vec![];
+    for line in lines {
+        let line = line?;
+        // A record as printed by the solve command. The tour of a record
+        // starts at the depot, which the permutation leaves implicit
+        if let Some(record) = formats.iter().find_map(|f| Record::parse(&line, *f)) {
+            return match record.tour {
+                Some(tour) => Ok(tour[1..].to_vec()),
+                None       => Err(CheckError::NoSolution),
+            };
+        }
+        // The headers and the detailed schedule lines are simply ignored
+        let header = formats.iter().any(|f| f.header().as_deref() == Some(line.trim()));
+        if header || line.contains('|') {
+            continue;
+        }
+        // A bare permutation (possibly spread over several lines)
+        for token in line.split_whitespace() {
+            bare.push(node(token)?);
+        }
+    }
+    if bare.is_empty() {
+        Err(CheckError::NoSolution)
+    } else {
+        Ok(bare)
+    }
+}
+fn node(token: &str) -> Result<usize, CheckError> {
+    token.parse::<usize>().map_err(|_| CheckError::NotANumber(token.to_string()))
+}
@@ -0,0 +1,263 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module confronts the records of a batch of runs with the best known
+//! solutions of the benchmark instances. These best known solutions are read
+//! from reference files (such as `sol_langevin.txt`) where each line holds
+//! the name of an instance file, the value of the best known solution, the
+//! number of constraint violations of that solution and the permutation of
+//! the customers; optionally followed by a comment (starting with '#').
+
+use std::{collections::HashMap, error, fmt, io::{self, BufRead, Lines}};
+
+use crate::report::Record;
+
+/// The tolerance used when comparing values which are not rounded (such as
+/// the values of the baseline records)
+const EPSILON: f64 = 1e-6;
+
+/// This enumeration lists the reasons why a reference file cannot be read
+#[derive(Debug)]
+pub enum ReferenceError {
+    /// The underlying reader failed
+    Io(io::Error),
+    /// The line names an instance but holds no value for it
+    MissingValue { line: usize },
+    /// Some token of the line was expected to be a number
+    NotANumber { line: usize, token: String },
+}
+impl fmt::Display for ReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReferenceError::Io(e) =>
+                write!(f, "i/o error: {}", e),
+            ReferenceError::MissingValue{line} =>
+                write!(f, "line {}: no value is given for the instance", line),
+            ReferenceError::NotANumber{line, token} =>
+                write!(f, "line {}: '{}' is not a number", line, token),
+        }
+    }
+}
+impl error::Error for ReferenceError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ReferenceError::Io(e) => Some(e),
+            _                     => None
+        }
+    }
+}
+impl From<io::Error> for ReferenceError {
+    fn from(e: io::Error) -> Self {
+        ReferenceError::Io(e)
+    }
+}
+
+/// The best known solution of an instance
+#[derive(Debug, Clone, PartialEq)]
+pub struct BestKnown {
+    /// The name of the instance file
+    pub instance   : String,
+    /// The value of the solution
+    pub value      : f64,
+    /// The margin within which a value matches this one. The values of the 
+    /// reference files are rounded: this is half a unit of the last decimal
+    /// printed in the file (0.005 for a value given with two decimals).
+    pub tolerance  : f64,
+    /// The number of constraints violated by the solution
+    pub violations : usize,
+    /// The permutation of the customers (without the depot)
+    pub permutation: Vec<usize>,
+    /// The comment of the line (who found the solution, when, ...)
+    pub comment    : Option<String>,
+}
+
+/// The best known solutions of a set of instances. These are indexed by the
+/// name of the instance file.
+#[derive(Debug, Clone, Default)]
+pub struct References {
+    entries: HashMap<String, BestKnown>,
+}
+impl References {
+    /// Adds all the solutions of the given reference file. A solution
+    /// replaces any solution that was previously known for the same instance.
+    pub fn load<B: BufRead>(&mut self, lines: Lines<B>) -> Result<(), ReferenceError> {
+        for (i, line) in lines.enumerate() {
+            let line = line?;
+            if let Some(best) = parse_line(i + 1, &line)? {
+                self.entries.insert(best.instance.clone(), best);
+            }
+        }
+        Ok(())
+    }
+    /// Returns the best known solution of the given instance. The instance
+    /// can either be named after its file or as it is in the records 
+    /// (benchmark/file).
+    pub fn get(&self, instance: &str) -> Option<&BestKnown> {
+        let name = instance.rsplit('/').next().unwrap_or(instance);
+        self.entries.get(name)
+    }
+    /// The number of instances having a known solution
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Returns true iff no solution is known
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Iterates over the known solutions (in no particular order)
+    pub fn iter(&self) -> impl Iterator<Item=&BestKnown> {
+        self.entries.values()
+    }
+}
+/// Parses one line of a reference file. Blank lines and comment lines yield
+/// no solution.
+fn parse_line(line: usize, text: &str) -> Result<Option<BestKnown>, ReferenceError> {
+    let (data, comment) = match text.find('#') {
+        Some(pos) => (&text[..pos], Some(text[pos + 1..].trim().to_string())),
+        None      => (text, None),
+    };
+    let mut tokens = data.split_whitespace();
+    let instance   = match tokens.next() {
+        Some(name) => name.to_string(),
+        None       => return Ok(None),
+    };
+    let number     = |token: &str| ReferenceError::NotANumber{line, token: token.to_string()};
+    let value      = tokens.next().ok_or(ReferenceError::MissingValue{line})?;
+    let tolerance  = tolerance(value);
+    let value      = value.parse::<f64>().map_err(|_| number(value))?;
+    let violations = match tokens.next() {
+        Some(token) => token.parse::<usize>().map_err(|_| number(token))?,
+        None        => 0,
+    };
+    let permutation= tokens
+        .map(|token| token.parse::<usize>().map_err(|_| number(token)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(BestKnown { instance, value, tolerance, violations, permutation, comment: comment.filter(|c| !c.is_empty()) }))
+}
+/// Returns half a unit of the last decimal of the given number (the margin
+/// within which a value matches that number once rounded)
+pub fn tolerance(token: &str) -> f64 {
+    let decimals = token.find('.').map_or(0, |dot| token[dot + 1..].len());
+    0.5 * 10_f64.powi(-(decimals as i32))
+}
+
+/// The conclusion drawn from the comparison of a record with what was known
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Nothing is known about the instance
+    Unknown,
+    /// The best solution is as good as the best known one
+    Match,
+    /// The best solution is better than the best known one (new best)
+    Improvement,
+    /// The best solution is worse than the best known one (or there is none)
+    WorseValue,
+    /// The instance had been proved optimal in the baseline but is not anymore
+    LostProof,
+    /// The solver pretends that no solution is better than its lower bound
+    /// whereas a better solution is known. In particular, this happens when
+    /// an optimum is "Proved" to be above the best known solution.
+    WrongClaim,
+}
+impl Verdict {
+    /// Returns true iff this verdict signals a regression
+    pub fn is_regression(self) -> bool {
+        matches!(self, Verdict::WorseValue | Verdict::LostProof)
+    }
+}
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Verdict::Unknown     => "unknown",
+            Verdict::Match       => "match",
+            Verdict::Improvement => "improvement",
+            Verdict::WorseValue  => "worse value",
+            Verdict::LostProof   => "lost proof",
+            Verdict::WrongClaim  => "wrong claim",
+        };
+        f.pad(text)
+    }
+}
+
+/// The comparison of one record with what was known about the instance
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// The name of the instance (benchmark/file)
+    pub instance  : String,
+    /// The status of the record
+    pub status    : String,
+    /// The value of the best solution of the record
+    pub value     : Option<f64>,
+    /// The value of the best known solution (either from the references or
+    /// from the baseline, whichever is the best)
+    pub best_known: Option<f64>,
+    /// The conclusion of the comparison
+    pub verdict   : Verdict,
+}
+
+/// Compares each record with the best known solutions of the references and,
+/// optionally, with the records of a baseline batch (typically an earlier 
+/// run of the solver). The baseline provides both known values and proofs
+/// of optimality.
+pub fn compare(references: &References, baseline: &[Record], records: &[Record]) -> Vec<Comparison> {
+    let baseline = baseline.iter()
+        .map(|r| (r.instance.as_str(), r))
+        .collect::<HashMap<_, _>>();
+
+    records.iter().map(|record| {
+        let before     = baseline.get(record.instance.as_str());
+        // A reference solution violating some constraint is no bound at all
+        let reference  = references.get(&record.instance).filter(|b| b.violations == 0);
+        let (best_known, epsilon) = best_of(
+            before.and_then(|b| b.upper_bound).map(|v| (v, EPSILON)),
+            reference.map(|b| (b.value, b.tolerance)));
+        let proved     = |r: &Record| r.status == "Proved";
+        let verdict    = match (record.upper_bound, best_known) {
+            (_, None) if before.is_none()                 => Verdict::Unknown,
+            (Some(_), None)                               => Verdict::Improvement,
+            (_, Some(k)) if record.lower_bound.is_some_and(|lb| lb > k + epsilon)
+                                                          => Verdict::WrongClaim,
+            (None, Some(_))                               => Verdict::WorseValue,
+            (Some(v), Some(k)) if v > k + epsilon         => Verdict::WorseValue,
+            _ if before.is_some_and(|b| proved(b)) && !proved(record)
+                                                          => Verdict::LostProof,
+            (Some(v), Some(k)) if v < k - epsilon         => Verdict::Improvement,
+            _                                             => Verdict::Match,
+        };
+        Comparison {
+            instance: record.instance.clone(),
+            status  : record.status.clone(),
+            value   : record.upper_bound,
+            best_known,
+            verdict,
+        }
+    })
+    .collect()
+}
+/// Returns the best (smallest) of the known values along with the tolerance
+/// of the source which supplied it. The first source wins the ties.
+fn best_of(a: Option<(f64, f64)>, b: Option<(f64, f64)>) -> (Option<f64>, f64) {
+    match (a, b) {
+        (Some(a), Some(b)) if b.0 < a.0 => (Some(b.0), b.1),
+        (Some(a), _)                    => (Some(a.0), a.1),
+        (None, Some(b))                 => (Some(b.0), b.1),
+        (None, None)                    => (None, EPSILON),
+    }
+}
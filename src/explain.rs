@@ -0,0 +1,143 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module explains why an instance has no feasible tour. It looks for a
+//! small set of nodes which cannot be served together, so that one knows
+//! which time window has to be moved. The obvious conflicts are read from
+//! the precedence graph (a node which cannot be reached in time, a node
+//! after which the tour cannot end in time, two nodes which must
+//! each precede the other). When there is none, the customers are removed
+//! one at a time and a customer is left out for good whenever the remaining
+//! ones still have no feasible tour (deletion filter). The optional 
+//! customers never make an instance infeasible: they are left aside.
+
+use std::fmt;
+
+use crate::{instance::{End, TSPTWInstance}, precedence::PrecedenceGraph};
+
+/// A set of nodes that cannot be served together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// The service of this node cannot start in time, even when it is the
+    /// first node visited
+    Unreachable(usize),
+    /// The salesman cannot get back to the depot (or reach the node where
+    /// the tour ends) in time after serving this node
+    Stranded(usize),
+    /// Each of these nodes must be visited before the other
+    Cycle(usize, usize),
+    /// These customers cannot all be served. The set is minimal (removing
+    /// any of them makes the instance feasible) unless the feasibility of
+    /// some subset could not be decided.
+    Subset { nodes: Vec<usize>, minimal: bool },
+}
+impl Conflict {
+    /// The nodes involved in this conflict
+    pub fn nodes(&self) -> Vec<usize> {
+        match self {
+            Conflict::Unreachable(node)     => vec![*node],
+            Conflict::Stranded(node)        => vec![*node],
+            Conflict::Cycle(i, j)           => vec![*i, *j],
+            Conflict::Subset{nodes, ..}     => nodes.clone(),
+        }
+    }
+}
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Conflict::Unreachable(node) =>
+                write!(f, "node {} cannot be reached in time", node),
+            Conflict::Stranded(node) =>
+                write!(f, "the tour cannot end in time after node {}", node),
+            Conflict::Cycle(i, j) =>
+                write!(f, "nodes {} and {} must each be visited before the other", i, j),
+            Conflict::Subset{nodes, minimal} => {
+                write!(f, "nodes")?;
+                for node in nodes.iter() {
+                    write!(f, " {}", node)?;
+                }
+                write!(f, " cannot all be served")?;
+                if !*minimal {
+                    write!(f, " (this set might not be minimal)")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns the conflicts found in the precedence graph of the instance
+pub fn obvious_conflicts(inst: &TSPTWInstance) -> Vec<Conflict> {
+    let graph = PrecedenceGraph::new(inst);
+    let end   = match inst.end {
+        End::Depot      => Some(0),
+        End::Node(node) => Some(node),
+        End::Free       => None,
+    };
+
+    let mandatory = inst.customers().filter(|j| !inst.is_optional(*j)).collect::<Vec<_>>();
+
+    let mut conflicts = vec![];
+    for &j in mandatory.iter() {
+        if graph.shortest(0, j) > inst.deadline(j) {
+            conflicts.push(Conflict::Unreachable(j));
+        } else if end.is_some_and(|e| inst.timewindows[j].earliest + graph.shortest(j, e) > inst.deadline(e)) {
+            conflicts.push(Conflict::Stranded(j));
+        }
+    }
+    for &i in mandatory.iter() {
+        for &j in mandatory.iter().filter(|j| **j > i) {
+            if graph.must_precede(i, j) && graph.must_precede(j, i) {
+                conflicts.push(Conflict::Cycle(i, j));
+            }
+        }
+    }
+    conflicts
+}
+
+/// Explains why the given instance (which is known to have no feasible tour)
+/// is infeasible. The `feasible` function tells whether an instance has a
+/// feasible tour (None when that could not be decided). It is only called 
+/// on subsets of the customers, when the precedence graph holds no obvious
+/// conflict.
+pub fn explain<F>(inst: &TSPTWInstance, mut feasible: F) -> Vec<Conflict>
+    where F: FnMut(&TSPTWInstance) -> Option<bool>
+{
+    let conflicts = obvious_conflicts(inst);
+    if !conflicts.is_empty() {
+        return conflicts;
+    }
+
+    let mut nodes   = inst.customers().filter(|j| !inst.is_optional(*j)).collect::<Vec<_>>();
+    let mut minimal = true;
+    let mut k       = 0;
+    while k < nodes.len() {
+        let mut subset = nodes.clone();
+        subset.remove(k);
+        match feasible(&inst.restrict(&subset)) {
+            Some(false) => nodes = subset,
+            Some(true)  => k += 1,
+            None        => {
+                minimal = false;
+                k += 1;
+            }
+        }
+    }
+    vec![Conflict::Subset{nodes, minimal}]
+}
@@ -21,7 +21,7 @@
 //! to improve the behavior of the branch-and-bound-MDD solver for travelling
 //! salesman problem with time windows.
 
-use ddo::{FrontierNode, Problem, LoadVars, Variable, VarSet, WidthHeuristic};
+use ddo::{FrontierNode, Problem, LoadVars, VarSet, WidthHeuristic};
 
 use crate::state::State;
 use crate::model::TSPTW;
@@ -42,7 +42,7 @@ impl LoadVars<State> for LoadVarsFromDepth {
         let mut ret = VarSet::all(self.nb_vars);
 
         for i in 0..depth {
-            ret.remove(Variable(i as usize));
+            ret.remove(TSPTW::variable_at(i as usize));
         }
         ret
     }
@@ -21,7 +21,7 @@
 //! and turn it into a structs usable in Rust. Chances are high that this 
 //! module will be of little to no interest to you.
 
-use std::{f32, fs::File, io::{BufRead, BufReader, Lines, Read}};
+use std::{convert::TryFrom, error, fmt, fs::File, io::{self, BufRead, BufReader, Lines, Read}};
 
 use ddo::Matrix;
 
@@ -39,73 +39,1068 @@ impl TimeWindow {
     }
 }
 
+/// This structure describes how late the service of a node may start: the
+/// time window of a node having a lateness is a soft constraint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Lateness {
+    /// The cost of each unit of time by which the service starts after the
+    /// end of the time window
+    pub penalty: usize,
+    /// How late the service may start at most (None when there is no limit)
+    pub max    : Option<usize>,
+}
+
+/// The travel time along an arc as a function of the time at which the 
+/// salesman leaves. It interpolates linearly between its breakpoints 
+/// (departure, travel time) and it is constant before the first one and 
+/// after the last one. Leaving later never means arriving earlier (FIFO).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TravelFunction {
+    breakpoints: Vec<(usize, usize)>,
+}
+impl TravelFunction {
+    /// Creates the function going through the given breakpoints. This 
+    /// returns None when there is no breakpoint, when the departures do not
+    /// increase or when leaving later would mean arriving earlier.
+    pub fn new(breakpoints: Vec<(usize, usize)>) -> Option<Self> {
+        let valid = !breakpoints.is_empty() && breakpoints.windows(2)
+            .all(|w| w[0].0 < w[1].0 && w[0].0 + w[0].1 <= w[1].0 + w[1].1);
+        if valid {
+            Some(Self { breakpoints })
+        } else {
+            None
+        }
+    }
+    /// The breakpoints (departure, travel time) of the function
+    pub fn breakpoints(&self) -> &[(usize, usize)] {
+        &self.breakpoints
+    }
+    /// Returns the travel time when leaving at the given time. It is rounded
+    /// down, which keeps the arrival times non decreasing.
+    pub fn at(&self, departure: usize) -> usize {
+        let bp = &self.breakpoints;
+        let k  = bp.iter().take_while(|b| b.0 <= departure).count();
+        if k == 0 {
+            return bp[0].1;
+        }
+        if k == bp.len() {
+            return bp[k - 1].1;
+        }
+        let (d0, t0) = (bp[k - 1].0 as isize, bp[k - 1].1 as isize);
+        let (d1, t1) = (bp[k].0 as isize, bp[k].1 as isize);
+        (t0 + ((t1 - t0) * (departure as isize - d0)).div_euclid(d1 - d0)) as usize
+    }
+    /// Returns the shortest travel time when leaving at some time between
+    /// `from` and `to` (the function is monotone between its breakpoints)
+    pub fn min_between(&self, from: usize, to: usize) -> usize {
+        self.breakpoints.iter()
+            .filter(|b| from < b.0 && b.0 < to)
+            .map(|b| b.1)
+            .fold(self.at(from).min(self.at(to)), usize::min)
+    }
+    /// Returns the shortest travel time, whatever the departure
+    pub fn shortest(&self) -> usize {
+        self.breakpoints.iter().map(|b| b.1).min().unwrap()
+    }
+    /// Applies the given change of unit to the breakpoints. The breakpoints
+    /// which then come too close to the previous one are dropped and the 
+    /// travel times are lengthened if need be to preserve the FIFO property.
+    fn rescale<F: Fn(usize) -> usize>(&self, rescale: F) -> Self {
+        let mut breakpoints: Vec<(usize, usize)> = vec![];
+        for &(departure, travel) in self.breakpoints.iter() {
+            let departure = rescale(departure);
+            let travel    = rescale(travel);
+            match breakpoints.last() {
+                Some(&(d, _)) if d >= departure => continue,
+                Some(&(d, t))                   => breakpoints.push((departure, travel.max((d + t).saturating_sub(departure)))),
+                None                            => breakpoints.push((departure, travel)),
+            }
+        }
+        Self { breakpoints }
+    }
+}
+
+/// This enumeration tells where the tour ends
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum End {
+    /// The salesman goes back to the depot
+    #[default]
+    Depot,
+    /// The salesman ends his tour at the given node, which is not a customer
+    /// (its time window constrains the arrival)
+    Node(usize),
+    /// The salesman ends his tour as soon as he is done with the last
+    /// customer
+    Free,
+}
+
 /// This structure represents the TSP with time window instane.
 #[derive(Clone)]
 pub struct TSPTWInstance {
     /// The number of nodes (including depot)
     pub nb_nodes   : u16, 
-    /// This is the distance matrix between any two nodes
+    /// This is the distance matrix between any two nodes. It also gives the
+    /// shortest time it takes to travel along each arc.
     pub distances  : Matrix<usize>,
-    /// This vector encodes the time windows to reach any vertex
-    pub timewindows: Vec<TimeWindow>
+    /// The time it takes to travel along each arc, as a function of the 
+    /// departure time (None when it is the distance between the two nodes)
+    pub travel_times: Matrix<Option<TravelFunction>>,
+    /// This vector encodes the time windows to reach any vertex. The service
+    /// of a node must start within that window (but it may end after it).
+    /// When a node has several time windows, this is their envelope.
+    pub timewindows: Vec<TimeWindow>,
+    /// The disjoint time windows of each node, sorted by opening time. A node
+    /// whose list is empty is open throughout its (single) time window. 
+    /// Either way, the service of a node never starts outside the window
+    /// given in `timewindows`.
+    pub slots      : Vec<Vec<TimeWindow>>,
+    /// The lateness allowed at each node (None when the time window of the
+    /// node is a hard constraint)
+    pub lateness   : Vec<Option<Lateness>>,
+    /// This vector encodes the duration of the service at any vertex
+    pub service_times: Vec<usize>,
+    /// This vector encodes the change of the load of the vehicle at any 
+    /// vertex: positive for a pickup and negative for a delivery. The value
+    /// of the depot is the load of the vehicle when it leaves the depot.
+    pub loads      : Vec<isize>,
+    /// The capacity of the vehicle (None when the load is not constrained)
+    pub capacity   : Option<usize>,
+    /// The number of vehicles. Each of them leaves the depot, serves some of
+    /// the customers and returns to the depot (when there are several 
+    /// vehicles, some of them may remain unused).
+    pub fleet      : usize,
+    /// The shift of each vehicle: the vehicle leaves the depot when its 
+    /// shift starts and it must be back before its shift ends. The vehicles
+    /// which have no shift leave the depot at time zero.
+    pub shifts     : Vec<TimeWindow>,
+    /// Where the tour ends. It may only end elsewhere than at the depot when
+    /// there is a single vehicle which has no shift.
+    pub end        : End,
+    /// The prize collected when a customer is served (None when the customer
+    /// must be served). The customers which have a prize are optional: the
+    /// prizes of those which are left out are added to the cost of the tour.
+    pub prizes     : Vec<Option<usize>>,
+    /// The weight of each node in the cumulative objective: the time at which
+    /// the service of a customer starts counts that many times (the weights
+    /// of the depot and of the end of the tour are ignored)
+    pub weights    : Vec<usize>,
+    /// The number of decimal digits which are kept when the (decimal) values
+    /// of the instance file are turned into the fixed point integers used
+    /// everywhere in the solver.
+    pub precision  : u32,
+    /// The pairs of customers (a, b) such that a must be visited before b.
+    /// Use `add_precedence` to make sure these never form a cycle.
+    pub precedences: Vec<(usize, usize)>,
 }
 
-impl From<File> for TSPTWInstance {
-    fn from(file: File) -> Self {
-        Self::from(BufReader::new(file))
+/// The line which opens the (optional) precedences section of a file
+pub const PRECEDENCES: &str = "PRECEDENCES";
+/// The keyword which gives the (optional) capacity of the vehicle
+pub const CAPACITY: &str = "CAPACITY";
+/// The line which opens the (optional) section listing the nodes which have
+/// several time windows
+pub const TIME_WINDOWS: &str = "TIME_WINDOWS";
+/// The line which opens the (optional) section listing the nodes which may
+/// be served late
+pub const LATENESS: &str = "LATENESS";
+/// The keyword which gives the (optional) number of vehicles
+pub const VEHICLES: &str = "VEHICLES";
+/// The line which opens the (optional) section listing the shift of each
+/// vehicle
+pub const SHIFTS: &str = "SHIFTS";
+/// The line which opens the (optional) section listing the customers which
+/// may be left out along with their prize
+pub const PRIZES: &str = "PRIZES";
+/// The line which opens the (optional) section listing the weight of some
+/// customers in the cumulative objective
+pub const WEIGHTS: &str = "WEIGHTS";
+/// The line which opens the (optional) section listing the arcs whose 
+/// travel time depends on the departure time
+pub const TRAVEL_TIMES: &str = "TRAVEL_TIMES";
+/// The keyword which gives the (optional) end of the tour: a node or FREE
+pub const END: &str = "END";
+/// The value of the END keyword when the tour ends at the last customer
+pub const FREE: &str = "FREE";
+
+/// The maximum number of decimal digits that are ever kept (more than that
+/// would needlessly eat up the headroom of an usize).
+pub const MAX_PRECISION: u32 = 9;
+
+impl TSPTWInstance {
+    /// Creates an instance having the given distances and time windows (one
+    /// per node, the depot first). All the other features are left out: the
+    /// tour is driven by a single vehicle with no capacity which goes back to
+    /// the depot, the services take no time, every customer must be served 
+    /// within its window, ... These are meant to be set afterwards (e.g. with
+    /// the struct update syntax).
+    pub fn new(distances: Matrix<usize>, timewindows: Vec<TimeWindow>) -> Self {
+        let n = timewindows.len();
+        TSPTWInstance {
+            nb_nodes     : n as u16,
+            distances,
+            travel_times : Matrix::new_default(n, n, None),
+            timewindows,
+            slots        : vec![vec![]; n],
+            lateness     : vec![None; n],
+            service_times: vec![0; n],
+            loads        : vec![0; n],
+            capacity     : None,
+            fleet        : 1,
+            shifts       : vec![],
+            end          : End::Depot,
+            prizes       : vec![None; n],
+            weights      : vec![1; n],
+            precision    : 0,
+            precedences  : vec![],
+        }
+    }
+    /// Returns the factor by which all the decimal values of the file were
+    /// multiplied to obtain their fixed point representation.
+    pub fn scale(&self) -> usize {
+        10_usize.pow(self.precision)
+    }
+    /// Converts a fixed point quantity (e.g. a cost or a bound) back to the 
+    /// unit in which the instance file was written.
+    pub fn to_decimal(&self, x: isize) -> f64 {
+        x as f64 / self.scale() as f64
+    }
+    /// Formats a fixed point quantity with exactly as many decimals as there
+    /// are in the fixed point representation.
+    pub fn format(&self, x: isize) -> String {
+        format!("{:.*}", self.precision as usize, self.to_decimal(x))
+    }
+    /// Changes the number of vehicles. This fails (and leaves the instance 
+    /// untouched) when there would be no vehicle, fewer vehicles than shifts
+    /// or several vehicles although the tour ends away from the depot.
+    pub fn set_fleet(&mut self, fleet: usize) -> Result<(), ParseError> {
+        let single = fleet == 1 && self.shifts.is_empty();
+        if fleet == 0 || self.shifts.len() > fleet || (self.end != End::Depot && !single) {
+            return Err(ParseError::IncompatibleFleet{fleet});
+        }
+        self.fleet = fleet;
+        Ok(())
+    }
+    /// Changes the precision of this instance and rescales all the distances
+    /// and time windows accordingly. Values are rounded to the nearest integer
+    /// when the precision is lowered.
+    pub fn set_precision(&mut self, precision: u32) {
+        let precision = precision.min(MAX_PRECISION);
+        let rescale   = |x: usize| {
+            if precision >= self.precision {
+                x * 10_usize.pow(precision - self.precision)
+            } else {
+                let div = 10_usize.pow(self.precision - precision);
+                (x + div / 2) / div
+            }
+        };
+
+        let n = self.nb_nodes as usize;
+        let mut distances    = Matrix::new_default(n, n, 0);
+        let mut travel_times = Matrix::new_default(n, n, None);
+        for i in 0..n {
+            for j in 0..n {
+                distances[(i, j)]    = rescale(self.distances[(i, j)]);
+                travel_times[(i, j)] = self.travel_times[(i, j)].as_ref().map(|f: &TravelFunction| f.rescale(rescale));
+            }
+        }
+        let timewindows = self.timewindows.iter()
+            .map(|tw| TimeWindow::new(rescale(tw.earliest), rescale(tw.latest)))
+            .collect();
+        let service_times = self.service_times.iter()
+            .map(|s| rescale(*s))
+            .collect();
+        let slots = self.slots.iter()
+            .map(|slots| slots.iter()
+                .map(|tw| TimeWindow::new(rescale(tw.earliest), rescale(tw.latest)))
+                .collect())
+            .collect();
+        let lateness = self.lateness.iter()
+            .map(|l| l.map(|l| Lateness { penalty: l.penalty, max: l.max.map(rescale) }))
+            .collect();
+        let shifts = self.shifts.iter()
+            .map(|tw| TimeWindow::new(rescale(tw.earliest), rescale(tw.latest)))
+            .collect();
+        let prizes = self.prizes.iter()
+            .map(|p| p.map(rescale))
+            .collect();
+
+        self.distances     = distances;
+        self.travel_times  = travel_times;
+        self.timewindows   = timewindows;
+        self.service_times = service_times;
+        self.slots         = slots;
+        self.lateness      = lateness;
+        self.shifts        = shifts;
+        self.prizes        = prizes;
+        self.precision     = precision;
+    }
+    /// Gives several disjoint time windows to the given node. The window of
+    /// the node becomes the envelope of these windows. This returns false 
+    /// (and leaves the instance untouched) when the windows are empty or 
+    /// when they overlap.
+    pub fn set_slots(&mut self, node: usize, mut slots: Vec<TimeWindow>) -> bool {
+        slots.sort_unstable_by_key(|tw| tw.earliest);
+        let valid = !slots.is_empty()
+            && slots.iter().all(|tw| tw.earliest <= tw.latest)
+            && slots.windows(2).all(|w| w[0].latest < w[1].earliest);
+        if valid {
+            self.timewindows[node] = TimeWindow::new(slots[0].earliest, slots[slots.len() - 1].latest);
+            self.slots[node]       = if slots.len() > 1 { slots } else { vec![] };
+        }
+        valid
+    }
+    /// Returns the time it takes to travel from node i to node j when leaving
+    /// i at the given time
+    pub fn travel_time(&self, i: usize, j: usize, departure: usize) -> usize {
+        match &self.travel_times[(i, j)] {
+            None    => self.distances[(i, j)],
+            Some(f) => f.at(departure),
+        }
+    }
+    /// Returns the shortest time it takes to travel from node i to node j when
+    /// leaving i at some time between `from` and `to`
+    pub fn min_travel_time(&self, i: usize, j: usize, from: usize, to: usize) -> usize {
+        match &self.travel_times[(i, j)] {
+            None    => self.distances[(i, j)],
+            Some(f) => f.min_between(from, to),
+        }
+    }
+    /// Returns true iff the travel time along some arc depends on the time
+    /// at which the salesman leaves
+    pub fn has_travel_functions(&self) -> bool {
+        let n = self.nb_nodes as usize;
+        (0..n).any(|i| (0..n).any(|j| self.travel_times[(i, j)].is_some()))
+    }
+    /// Returns the earliest time (not before `time`) at which the service of
+    /// the given node can start, or None when it is too late to serve it.
+    pub fn next_opening(&self, node: usize, time: usize) -> Option<usize> {
+        self.next_opening_within(node, self.timewindows[node], time)
+    }
+    /// Returns the earliest time (not before `time`) at which the service of
+    /// the given node can start within the given window, or None when it is
+    /// too late to serve it.
+    pub(crate) fn next_opening_within(&self, node: usize, tw: TimeWindow, time: usize) -> Option<usize> {
+        if time > tw.latest {
+            // A late service starts as soon as the salesman arrives
+            return match self.lateness[node] {
+                None    => None,
+                Some(l) => Some(time).filter(|t| l.max.is_none_or(|max| *t <= tw.latest + max)),
+            };
+        }
+        let time = time.max(tw.earliest);
+        if self.slots[node].is_empty() {
+            return Some(time);
+        }
+        self.slots[node].iter()
+            .find(|slot| slot.latest >= time)
+            .map(|slot| time.max(slot.earliest))
+            .filter(|start| *start <= tw.latest)
+    }
+    /// Returns the latest time at which the service of the given node may 
+    /// start (which is past the end of its time window when it may be late)
+    pub fn deadline(&self, node: usize) -> usize {
+        let latest = self.timewindows[node].latest;
+        match self.lateness[node] {
+            None    => latest,
+            Some(l) => l.max.map_or(usize::MAX, |max| latest + max),
+        }
+    }
+    /// Returns the penalty incurred when the service of the given node 
+    /// starts at the given time
+    pub fn lateness_cost(&self, node: usize, start: usize) -> usize {
+        match self.lateness[node] {
+            None    => 0,
+            Some(l) => l.penalty * start.saturating_sub(self.timewindows[node].latest),
+        }
+    }
+    /// Returns true iff the time window of some node is a soft constraint
+    pub fn has_soft_windows(&self) -> bool {
+        self.lateness.iter().any(Option::is_some)
+    }
+    /// Turns the time windows of all nodes into soft constraints (unless the
+    /// lateness of a node is already known). The maximum lateness is 
+    /// expressed in the unit of the instance file.
+    pub fn soften(&mut self, penalty: usize, max: Option<f64>) {
+        let max = max.map(|m| fixed(m, self.precision));
+        for lateness in self.lateness.iter_mut().filter(|l| l.is_none()) {
+            *lateness = Some(Lateness { penalty, max });
+        }
+    }
+    /// Returns the least time spent waiting for the given node to open when
+    /// it is reached at some time between `earliest` and `latest`.
+    pub fn min_waiting(&self, node: usize, earliest: usize, latest: usize) -> usize {
+        match self.next_opening(node, earliest) {
+            Some(start) if start > latest => start - latest,
+            _                             => 0,
+        }
+    }
+    /// Returns the shift of the given vehicle. A vehicle which has no shift
+    /// leaves the depot at time zero and is only constrained by the time 
+    /// window of the depot.
+    pub fn shift(&self, vehicle: usize) -> TimeWindow {
+        self.shifts.get(vehicle).copied()
+            .unwrap_or_else(|| TimeWindow::new(0, self.deadline(0)))
+    }
+    /// Returns true iff the given node is a customer (that is, neither the
+    /// depot nor the node where the tour ends)
+    pub fn is_customer(&self, node: usize) -> bool {
+        node != 0 && self.end != End::Node(node)
+    }
+    /// The number of customers to serve
+    pub fn nb_customers(&self) -> usize {
+        match self.end {
+            End::Node(_) => self.nb_nodes as usize - 2,
+            _            => self.nb_nodes as usize - 1,
+        }
+    }
+    /// Iterates over the customers in increasing order
+    pub fn customers(&self) -> impl Iterator<Item=usize> + '_ {
+        (1..self.nb_nodes as usize).filter(move |i| self.is_customer(*i))
+    }
+    /// Returns true iff the given node is a customer which may be left out
+    pub fn is_optional(&self, node: usize) -> bool {
+        self.prizes[node].is_some()
+    }
+    /// Returns the prize collected when the given node is served
+    pub fn prize(&self, node: usize) -> usize {
+        self.prizes[node].unwrap_or(0)
+    }
+    /// Returns true iff some customers may be left out
+    pub fn has_prizes(&self) -> bool {
+        self.prizes.iter().any(Option::is_some)
+    }
+    /// Returns true iff some customer weighs more (or less) than the others
+    pub fn has_weights(&self) -> bool {
+        self.customers().any(|i| self.weights[i] != 1)
+    }
+    /// Returns the sum of the prizes of all customers
+    pub fn total_prize(&self) -> usize {
+        self.prizes.iter().flatten().sum()
+    }
+    /// Returns the instance made of the depot and the given customers only
+    /// (in that order: the k-th customer becomes node k+1). The node where
+    /// the tour ends, if any, comes last. The precedences between the 
+    /// customers which are left out are dropped.
+    pub fn restrict(&self, customers: &[usize]) -> TSPTWInstance {
+        let end   = match self.end {
+            End::Node(node) => Some(node),
+            _               => None,
+        };
+        let nodes = std::iter::once(0).chain(customers.iter().copied()).chain(end).collect::<Vec<_>>();
+        let n     = nodes.len();
+        let mut distances    = Matrix::new_default(n, n, 0);
+        let mut travel_times = Matrix::new_default(n, n, None);
+        for (i, a) in nodes.iter().enumerate() {
+            for (j, b) in nodes.iter().enumerate() {
+                distances[(i, j)]    = self.distances[(*a, *b)];
+                travel_times[(i, j)] = self.travel_times[(*a, *b)].clone();
+            }
+        }
+        let rank = |node: usize| nodes.iter().position(|x| *x == node);
+        let precedences = self.precedences.iter()
+            .filter_map(|(before, after)| Some((rank(*before)?, rank(*after)?)))
+            .collect();
+        TSPTWInstance {
+            nb_nodes     : n as u16,
+            distances,
+            travel_times,
+            timewindows  : nodes.iter().map(|x| self.timewindows[*x]).collect(),
+            slots        : nodes.iter().map(|x| self.slots[*x].clone()).collect(),
+            lateness     : nodes.iter().map(|x| self.lateness[*x]).collect(),
+            service_times: nodes.iter().map(|x| self.service_times[*x]).collect(),
+            loads        : nodes.iter().map(|x| self.loads[*x]).collect(),
+            capacity     : self.capacity,
+            fleet        : self.fleet,
+            shifts       : self.shifts.clone(),
+            end          : match self.end {
+                End::Node(_) => End::Node(n - 1),
+                end          => end,
+            },
+            prizes       : nodes.iter().map(|x| self.prizes[*x]).collect(),
+            weights      : nodes.iter().map(|x| self.weights[*x]).collect(),
+            precision    : self.precision,
+            precedences,
+        }
+    }
+    /// Requires customer `before` to be visited before customer `after`. 
+    /// This fails when one of the nodes is not a customer, when it may be
+    /// left out or when the new precedence would close a cycle (no tour 
+    /// could then satisfy all the precedences).
+    pub fn add_precedence(&mut self, before: usize, after: usize) -> Result<(), PrecedenceError> {
+        for &node in [before, after].iter() {
+            if node == 0 {
+                return Err(PrecedenceError::Depot);
+            }
+            if node >= self.nb_nodes as usize {
+                return Err(PrecedenceError::UnknownNode(node));
+            }
+            if self.end == End::Node(node) {
+                return Err(PrecedenceError::End(node));
+            }
+            if self.is_optional(node) {
+                return Err(PrecedenceError::Optional(node));
+            }
+        }
+        if let Some(chain) = self.precedence_chain(after, before) {
+            return Err(PrecedenceError::Cycle(chain));
+        }
+        if !self.precedences.contains(&(before, after)) {
+            self.precedences.push((before, after));
+        }
+        Ok(())
+    }
+    /// Returns a chain of customers leading from `from` to `to` where each 
+    /// customer must be visited before the next one (None if there is no
+    /// such chain).
+    fn precedence_chain(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let n           = self.nb_nodes as usize;
+        let mut parent  = vec![None; n];
+        let mut visited = vec![false; n];
+        let mut stack   = vec![from];
+        visited[from]   = true;
+        while let Some(node) = stack.pop() {
+            if node == to {
+                let mut chain = vec![to];
+                let mut cur   = to;
+                while let Some(p) = parent[cur] {
+                    chain.push(p);
+                    cur = p;
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            for &(_, next) in self.precedences.iter().filter(|(a, _)| *a == node) {
+                if !visited[next] {
+                    visited[next] = true;
+                    parent[next]  = Some(node);
+                    stack.push(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// This enumeration lists the reasons why a precedence cannot be imposed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrecedenceError {
+    /// This node does not exist in the instance
+    UnknownNode(usize),
+    /// The depot is always visited first and last: it cannot be ordered
+    Depot,
+    /// The tour ends at this node: it cannot be ordered
+    End(usize),
+    /// This customer may be left out: it cannot be ordered
+    Optional(usize),
+    /// The precedence would close this cycle (each node of the cycle must 
+    /// be visited before the next one and the last one before the first)
+    Cycle(Vec<usize>),
+}
+impl fmt::Display for PrecedenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrecedenceError::UnknownNode(node) =>
+                write!(f, "node {} does not exist", node),
+            PrecedenceError::Depot =>
+                write!(f, "the depot cannot be ordered"),
+            PrecedenceError::End(node) =>
+                write!(f, "node {} ends the tour: it cannot be ordered", node),
+            PrecedenceError::Optional(node) =>
+                write!(f, "node {} may be left out: it cannot be ordered", node),
+            PrecedenceError::Cycle(nodes) => {
+                write!(f, "cyclic precedences:")?;
+                for node in nodes.iter().chain(nodes.first()) {
+                    write!(f, " {}", node)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+impl error::Error for PrecedenceError {}
+
+/// This enumeration lists all the reasons why an instance file could not be
+/// turned into a `TSPTWInstance`. Unless stated otherwise, the line and column
+/// numbers are 1-based and refer to the position in the original file 
+/// (comments and blank lines included).
+#[derive(Debug)]
+pub enum ParseError {
+    /// The underlying reader failed
+    Io(io::Error),
+    /// The first line does not start with a strictly positive node count 
+    /// (or the file holds no data at all)
+    BadNodeCount { line: usize, column: usize, token: String },
+    /// A row of the distance matrix does not hold exactly one entry per node
+    /// (or a line does not hold the expected number of values)
+    WrongRowLength { line: usize, expected: usize, found: usize },
+    /// The file ended before all rows of the distance matrix were read
+    MissingMatrixRow { line: usize, node: usize },
+    /// The file ended (or a line was truncated) before the time window of 
+    /// some node could be read
+    MissingTimeWindow { line: usize, node: usize },
+    /// Some token which should have been a number could not be parsed
+    NotANumber { line: usize, column: usize, token: String },
+    /// The precedence given on this line cannot be imposed
+    InvalidPrecedence { line: usize, error: PrecedenceError },
+    /// The node mentioned on this line does not exist
+    UnknownNode { line: usize, node: usize },
+    /// The number of vehicles is not a positive integer
+    BadFleet { line: usize, column: usize, token: String },
+    /// There are more shifts than vehicles (the line is that of the first 
+    /// shift in excess)
+    TooManyShifts { line: usize, fleet: usize },
+    /// The end of the tour is neither a node nor FREE
+    BadEnd { line: usize, column: usize, token: String },
+    /// The tour ends elsewhere than at the depot although there are several
+    /// vehicles or some vehicle has a shift
+    EndWithFleet { line: usize },
+    /// The node given on this line is not a customer: it cannot have a prize
+    BadPrize { line: usize, node: usize },
+    /// The travel times given on this line are invalid: a departure is 
+    /// missing, the departures do not increase, leaving later would mean 
+    /// arriving earlier or some travel time is shorter than the distance
+    BadTravelTimes { line: usize, from: usize, to: usize },
+    /// The time windows given on this line are invalid: the node does not
+    /// exist, a bound is missing or the windows are not disjoint intervals
+    BadTimeWindows { line: usize, node: usize },
+    /// The nodes are not listed in order: this line gives the data of some
+    /// other node than the expected one
+    MisnumberedNode { line: usize, expected: usize, found: String },
+    /// This line comes after the time windows of all nodes but it belongs
+    /// to no known section
+    UnexpectedLine { line: usize },
+    /// The number of vehicles does not suit the instance: there is no vehicle,
+    /// there are more shifts than vehicles or the tour ends away from the 
+    /// depot although there are several vehicles or shifts. (This error is
+    /// not bound to a line since the fleet may be set from elsewhere)
+    IncompatibleFleet { fleet: usize },
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(e) => 
+                write!(f, "i/o error: {}", e),
+            ParseError::BadNodeCount{line, column, token} => 
+                write!(f, "line {}, column {}: invalid number of nodes '{}'", line, column, token),
+            ParseError::WrongRowLength{line, expected, found} => 
+                write!(f, "line {}: expected {} values but found {}", line, expected, found),
+            ParseError::MissingMatrixRow{line, node} => 
+                write!(f, "line {}: missing distance matrix row for node {}", line, node),
+            ParseError::MissingTimeWindow{line, node} =>
+                write!(f, "line {}: missing time window for node {}", line, node),
+            ParseError::NotANumber{line, column, token} =>
+                write!(f, "line {}, column {}: '{}' is not a number", line, column, token),
+            ParseError::InvalidPrecedence{line, error} =>
+                write!(f, "line {}: {}", line, error),
+            ParseError::UnknownNode{line, node} =>
+                write!(f, "line {}: node {} does not exist", line, node),
+            ParseError::BadFleet{line, column, token} =>
+                write!(f, "line {}, column {}: invalid number of vehicles '{}'", line, column, token),
+            ParseError::TooManyShifts{line, fleet} =>
+                write!(f, "line {}: there are more shifts than vehicles ({})", line, fleet),
+            ParseError::BadEnd{line, column, token} =>
+                write!(f, "line {}, column {}: invalid end of the tour '{}'", line, column, token),
+            ParseError::EndWithFleet{line} =>
+                write!(f, "line {}: the tour can only end away from the depot with a single vehicle which has no shift", line),
+            ParseError::BadPrize{line, node} =>
+                write!(f, "line {}: node {} is not a customer, it cannot have a prize", line, node),
+            ParseError::BadTravelTimes{line, from, to} =>
+                write!(f, "line {}: invalid travel times from node {} to node {}", line, from, to),
+            ParseError::BadTimeWindows{line, node} =>
+                write!(f, "line {}: invalid time windows for node {}", line, node),
+            ParseError::MisnumberedNode{line, expected, found} =>
+                write!(f, "line {}: expected the data of node {} but found node '{}'", line, expected, found),
+            ParseError::UnexpectedLine{line} =>
+                write!(f, "line {}: unexpected line after the time windows of all nodes", line),
+            ParseError::IncompatibleFleet{fleet} =>
+                write!(f, "the instance cannot be served by {} vehicle(s)", fleet),
+        }
+    }
+}
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseError::Io(e)                        => Some(e),
+            ParseError::InvalidPrecedence{error, ..} => Some(error),
+            _                                        => None
+        }
+    }
+}
+impl From<io::Error> for ParseError {
+    fn from(e: io::Error) -> Self {
+        ParseError::Io(e)
+    }
+}
+
+impl TryFrom<File> for TSPTWInstance {
+    type Error = ParseError;
+
+    fn try_from(file: File) -> Result<Self, ParseError> {
+        Self::try_from(BufReader::new(file))
     }
 }
-impl <S: Read> From<BufReader<S>> for TSPTWInstance {
-    fn from(buf: BufReader<S>) -> Self {
-        Self::from(buf.lines())
+impl <S: Read> TryFrom<BufReader<S>> for TSPTWInstance {
+    type Error = ParseError;
+
+    fn try_from(buf: BufReader<S>) -> Result<Self, ParseError> {
+        Self::try_from(buf.lines())
     }
 }
-impl <B: BufRead> From<Lines<B>> for TSPTWInstance {
-    fn from(lines: Lines<B>) -> Self {
+impl <B: BufRead> TryFrom<Lines<B>> for TSPTWInstance {
+    type Error = ParseError;
+
+    fn try_from(lines: Lines<B>) -> Result<Self, ParseError> {
         let mut lc         = 0;
+        let mut ln         = 0;
         let mut nb_nodes   = 0_u16;
-        let mut distances  = Matrix::new_default(nb_nodes as usize, nb_nodes as usize, 0);
+        let mut precision  = 0_u32;
+        let mut distances  = vec![];
         let mut timewindows= vec![];
+        let mut precedences= vec![];
+        let mut section    = None;
+        let mut slots      = vec![];
+        let mut lateness   = vec![];
+        let mut capacity   = None;
+        let mut fleet      = None;
+        let mut shifts     = vec![];
+        let mut end        = None;
+        let mut prizes     = vec![];
+        let mut weights    = vec![];
+        let mut travels    = vec![];
 
         for line in lines {
-            let line = line.unwrap();
-            let line = line.trim();
+            let line = line?;
+            ln += 1;
 
             // skip comment lines
-            if line.starts_with('#') || line.is_empty() {
+            if line.trim().starts_with('#') || line.trim().is_empty() {
                 continue;
             }
             
            // First line is the number of nodes
            if lc == 0 { 
-               nb_nodes  = line.split_whitespace().next().unwrap().to_string().parse::<u16>().unwrap();
-               distances = Matrix::new_default(nb_nodes as usize, nb_nodes as usize, 0);
+               let (column, token) = tokens(&line).next().unwrap();
+               nb_nodes  = match token.parse::<u16>() {
+                   Ok(n) if n > 0 => n,
+                   _ => return Err(ParseError::BadNodeCount{line: ln, column, token: token.to_string()}),
+               };
            }
            // The next 'nb_nodes' lines represent the distances matrix
            else if (1..=nb_nodes).contains(&lc) {
-               let i = (lc - 1) as usize;
-               for (j, distance) in line.split_whitespace().enumerate() {
-                    let distance = distance.to_string().parse::<f32>().unwrap();
-                    let distance = (distance * 10000.0) as usize;
-                    distances[(i, j)] = distance;
+               let mut found = 0;
+               for (j, (column, token)) in tokens(&line).enumerate() {
+                    if j < nb_nodes as usize {
+                        distances.push(number(ln, column, token)?);
+                        precision = precision.max(decimals(token));
+                    }
+                    found += 1;
+               }
+               if found != nb_nodes as usize {
+                   return Err(ParseError::WrongRowLength{line: ln, expected: nb_nodes as usize, found});
+               }
+           }
+           // The time windows may be followed by the capacity of the vehicle,
+           // the number of vehicles and by sections which list the 
+           // precedences between customers (one 'before after' pair per line),
+           // the nodes having several time windows (one 'node earliest latest
+           // earliest latest...' line per node), the nodes which may be 
+           // served late (one 'node penalty [max lateness]' line per node), 
+           // the customers which may be left out (one 'node prize' line per
+           // node), the weights of the customers (one 'node weight' line per
+           // node), the arcs whose travel time varies (one 'from to departure
+           // travel departure travel...' line per arc) or the shifts of the
+           // vehicles (one 'earliest latest' line each).
+           // The end of the tour may be given too (a node or FREE)
+           else if timewindows.len() == nb_nodes as usize && [PRECEDENCES, TIME_WINDOWS, LATENESS, PRIZES, WEIGHTS, TRAVEL_TIMES, SHIFTS].contains(&line.trim()) {
+               section = Some(line.trim().to_string());
+           }
+           else if timewindows.len() == nb_nodes as usize && tokens(&line).next().map(|(_, token)| token) == Some(CAPACITY) {
+               let mut tokens = tokens(&line).skip(1);
+               let (column, token) = tokens.next()
+                   .ok_or(ParseError::WrongRowLength{line: ln, expected: 2, found: 1})?;
+               capacity = Some(token.parse::<usize>()
+                   .map_err(|_| ParseError::NotANumber{line: ln, column, token: token.to_string()})?);
+               section  = None;
+           }
+           else if timewindows.len() == nb_nodes as usize && tokens(&line).next().map(|(_, token)| token) == Some(VEHICLES) {
+               let mut tokens = tokens(&line).skip(1);
+               let (column, token) = tokens.next()
+                   .ok_or(ParseError::WrongRowLength{line: ln, expected: 2, found: 1})?;
+               fleet   = match token.parse::<usize>() {
+                   Ok(k) if k > 0 => Some((ln, k)),
+                   _ => return Err(ParseError::BadFleet{line: ln, column, token: token.to_string()})
+               };
+               section = None;
+           }
+           else if timewindows.len() == nb_nodes as usize && tokens(&line).next().map(|(_, token)| token) == Some(END) {
+               let mut tokens = tokens(&line).skip(1);
+               let (column, token) = tokens.next()
+                   .ok_or(ParseError::WrongRowLength{line: ln, expected: 2, found: 1})?;
+               end     = match (token, token.parse::<usize>()) {
+                   (FREE, _)                                 => Some((ln, End::Free)),
+                   (_, Ok(0))                                => Some((ln, End::Depot)),
+                   (_, Ok(node)) if node < nb_nodes as usize => Some((ln, End::Node(node))),
+                   _ => return Err(ParseError::BadEnd{line: ln, column, token: token.to_string()})
+               };
+               section = None;
+           }
+           else if section.as_deref() == Some(SHIFTS) {
+               let tokens = tokens(&line).collect::<Vec<_>>();
+               if tokens.len() != 2 {
+                   return Err(ParseError::WrongRowLength{line: ln, expected: 2, found: tokens.len()});
+               }
+               precision = precision.max(decimals(tokens[0].1)).max(decimals(tokens[1].1));
+               shifts.push((ln, number(ln, tokens[0].0, tokens[0].1)?, number(ln, tokens[1].0, tokens[1].1)?));
+           }
+           else if section.as_deref() == Some(PRECEDENCES) {
+               let pair = tokens(&line)
+                   .map(|(column, token)| token.parse::<usize>()
+                       .map_err(|_| ParseError::NotANumber{line: ln, column, token: token.to_string()}))
+                   .collect::<Result<Vec<_>, _>>()?;
+               if pair.len() != 2 {
+                   return Err(ParseError::WrongRowLength{line: ln, expected: 2, found: pair.len()});
+               }
+               precedences.push((ln, pair[0], pair[1]));
+           }
+           else if section.as_deref() == Some(TIME_WINDOWS) {
+               let mut tokens = tokens(&line);
+               let (column, token) = tokens.next().unwrap();
+               let node = token.parse::<usize>()
+                   .map_err(|_| ParseError::NotANumber{line: ln, column, token: token.to_string()})?;
+               if node >= nb_nodes as usize {
+                   return Err(ParseError::BadTimeWindows{line: ln, node});
+               }
+               let mut bounds = vec![];
+               for (column, token) in tokens {
+                   bounds.push(number(ln, column, token)?);
+                   precision = precision.max(decimals(token));
+               }
+               if bounds.is_empty() || bounds.len() % 2 != 0 {
+                   return Err(ParseError::BadTimeWindows{line: ln, node});
+               }
+               slots.push((ln, node, bounds));
+           }
+           else if section.as_deref() == Some(LATENESS) {
+               let tokens = tokens(&line).collect::<Vec<_>>();
+               if tokens.len() != 2 && tokens.len() != 3 {
+                   return Err(ParseError::WrongRowLength{line: ln, expected: 3, found: tokens.len()});
+               }
+               let ints = tokens[..2].iter()
+                   .map(|(column, token)| token.parse::<usize>()
+                       .map_err(|_| ParseError::NotANumber{line: ln, column: *column, token: token.to_string()}))
+                   .collect::<Result<Vec<_>, _>>()?;
+               if ints[0] >= nb_nodes as usize {
+                   return Err(ParseError::UnknownNode{line: ln, node: ints[0]});
                }
+               let max = match tokens.get(2) {
+                   None    => None,
+                   Some(m) => {
+                       precision = precision.max(decimals(m.1));
+                       Some(number(ln, m.0, m.1)?)
+                   }
+               };
+               lateness.push((ints[0], ints[1], max));
+           }
+           else if section.as_deref() == Some(PRIZES) {
+               let tokens = tokens(&line).collect::<Vec<_>>();
+               if tokens.len() != 2 {
+                   return Err(ParseError::WrongRowLength{line: ln, expected: 2, found: tokens.len()});
+               }
+               let node = tokens[0].1.parse::<usize>()
+                   .map_err(|_| ParseError::NotANumber{line: ln, column: tokens[0].0, token: tokens[0].1.to_string()})?;
+               if node >= nb_nodes as usize {
+                   return Err(ParseError::UnknownNode{line: ln, node});
+               }
+               precision = precision.max(decimals(tokens[1].1));
+               prizes.push((ln, node, number(ln, tokens[1].0, tokens[1].1)?));
+           }
+           else if section.as_deref() == Some(WEIGHTS) {
+               let ints = tokens(&line)
+                   .map(|(column, token)| token.parse::<usize>()
+                       .map_err(|_| ParseError::NotANumber{line: ln, column, token: token.to_string()}))
+                   .collect::<Result<Vec<_>, _>>()?;
+               if ints.len() != 2 {
+                   return Err(ParseError::WrongRowLength{line: ln, expected: 2, found: ints.len()});
+               }
+               if ints[0] >= nb_nodes as usize {
+                   return Err(ParseError::UnknownNode{line: ln, node: ints[0]});
+               }
+               weights.push((ints[0], ints[1]));
+           }
+           else if section.as_deref() == Some(TRAVEL_TIMES) {
+               let tokens = tokens(&line).collect::<Vec<_>>();
+               let arc    = tokens.iter().take(2)
+                   .map(|(column, token)| token.parse::<usize>()
+                       .map_err(|_| ParseError::NotANumber{line: ln, column: *column, token: token.to_string()}))
+                   .collect::<Result<Vec<_>, _>>()?;
+               if arc.len() != 2 {
+                   return Err(ParseError::WrongRowLength{line: ln, expected: 4, found: arc.len()});
+               }
+               let (from, to) = (arc[0], arc[1]);
+               if from >= nb_nodes as usize || to >= nb_nodes as usize {
+                   return Err(ParseError::UnknownNode{line: ln, node: from.max(to)});
+               }
+               let mut bounds = vec![];
+               for (column, token) in tokens[2..].iter() {
+                   bounds.push(number(ln, *column, token)?);
+                   precision = precision.max(decimals(token));
+               }
+               if from == to || bounds.is_empty() || bounds.len() % 2 != 0 {
+                   return Err(ParseError::BadTravelTimes{line: ln, from, to});
+               }
+               travels.push((ln, from, to, bounds));
            }
            // Finally, the last 'nb_nodes' lines impose the time windows constraints
+           // (optionally followed by the service time at the node and by the
+           // change of the load of the vehicle at that node)
            else {
-               let mut tokens = line.split_whitespace();
-               let earliest   = tokens.next().unwrap().to_string().parse::<f32>().unwrap();
-               let latest     = tokens.next().unwrap().to_string().parse::<f32>().unwrap();
+               let mut tokens = tokens(&line);
+               let node       = timewindows.len();
+               if node == nb_nodes as usize {
+                   return Err(ParseError::UnexpectedLine{line: ln});
+               }
+               let earliest   = tokens.next().ok_or(ParseError::MissingTimeWindow{line: ln, node})?;
+               let latest     = tokens.next().ok_or(ParseError::MissingTimeWindow{line: ln, node})?;
+               let service    = tokens.next();
+               let load       = match tokens.next() {
+                   None    => 0,
+                   Some(l) => integer(ln, l.0, l.1)?
+               };
 
-               let earliest   = (earliest * 10000.0) as usize;
-               let latest     = (latest   * 10000.0) as usize;
+               precision      = precision.max(decimals(earliest.1)).max(decimals(latest.1));
+               let earliest   = number(ln, earliest.0, earliest.1)?;
+               let latest     = number(ln, latest.0,   latest.1)?;
+               let service    = match service {
+                   None    => 0.0,
+                   Some(s) => {
+                       precision = precision.max(decimals(s.1));
+                       number(ln, s.0, s.1)?
+                   }
+               };
 
-               let timewind   = TimeWindow::new(earliest, latest);
-               timewindows.push(timewind);
+               timewindows.push((earliest, latest, service, load));
            }
             
             lc += 1;
         }
 
-        TSPTWInstance{nb_nodes, distances, timewindows}
+        // Check that the file was not truncated
+        if lc == 0 {
+            return Err(ParseError::BadNodeCount{line: ln + 1, column: 1, token: String::new()});
+        }
+        if lc <= nb_nodes {
+            return Err(ParseError::MissingMatrixRow{line: ln + 1, node: (lc - 1) as usize});
+        }
+        if timewindows.len() < nb_nodes as usize {
+            return Err(ParseError::MissingTimeWindow{line: ln + 1, node: timewindows.len()});
+        }
+
+        // Only now that the precision is known can we convert everything to
+        // fixed point
+        let n         = nb_nodes as usize;
+        let precision = precision.min(MAX_PRECISION);
+        let mut dist  = Matrix::new_default(n, n, 0);
+        for (k, d) in distances.into_iter().enumerate() {
+            dist[(k / n, k % n)] = fixed(d, precision);
+        }
+        let service_times = timewindows.iter()
+            .map(|(_, _, s, _)| fixed(*s, precision))
+            .collect();
+        let loads = timewindows.iter()
+            .map(|(_, _, _, l)| *l)
+            .collect();
+        let timewindows = timewindows.into_iter()
+            .map(|(e, l, _, _)| TimeWindow::new(fixed(e, precision), fixed(l, precision)))
+            .collect();
+
+        let mut inst = TSPTWInstance{service_times, loads, capacity, precision, ..TSPTWInstance::new(dist, timewindows)};
+
+        // By default, there is one vehicle per shift (and at least one)
+        let fleet   = fleet.map_or(shifts.len().max(1), |(_, k)| k);
+        if shifts.len() > fleet {
+            return Err(ParseError::TooManyShifts{line: shifts[fleet].0, fleet});
+        }
+        inst.shifts = shifts.into_iter()
+            .map(|(_, e, l)| TimeWindow::new(fixed(e, precision), fixed(l, precision)))
+            .collect();
+        // Only the end of the tour may still conflict with the fleet
+        if let Some((_, end)) = end {
+            inst.end = end;
+        }
+        inst.set_fleet(fleet).map_err(|e| match end {
+            Some((line, _)) => ParseError::EndWithFleet{line},
+            None            => e,
+        })?;
+        for (line, node, prize) in prizes {
+            if !inst.is_customer(node) {
+                return Err(ParseError::BadPrize{line, node});
+            }
+            inst.prizes[node] = Some(fixed(prize, precision));
+        }
+        for (line, from, to, bounds) in travels {
+            let breakpoints = bounds.chunks(2)
+                .map(|b| (fixed(b[0], precision), fixed(b[1], precision)))
+                .collect();
+            match TravelFunction::new(breakpoints) {
+                Some(f) if f.shortest() >= inst.distances[(from, to)] => inst.travel_times[(from, to)] = Some(f),
+                _ => return Err(ParseError::BadTravelTimes{line, from, to}),
+            }
+        }
+        for (node, weight) in weights {
+            inst.weights[node] = weight;
+        }
+        for (node, penalty, max) in lateness {
+            inst.lateness[node] = Some(Lateness { penalty, max: max.map(|m| fixed(m, precision)) });
+        }
+        for (line, node, bounds) in slots {
+            let windows = bounds.chunks(2)
+                .map(|b| TimeWindow::new(fixed(b[0], precision), fixed(b[1], precision)))
+                .collect();
+            if !inst.set_slots(node, windows) {
+                return Err(ParseError::BadTimeWindows{line, node});
+            }
+        }
+        for (line, before, after) in precedences {
+            inst.add_precedence(before, after)
+                .map_err(|error| ParseError::InvalidPrecedence{line, error})?;
+        }
+        Ok(inst)
+    }
+}
+
+/// Iterates over the whitespace separated tokens of a line along with the 
+/// (1-based) column where each of them starts.
+pub(crate) fn tokens(line: &str) -> impl Iterator<Item=(usize, &str)> {
+    line.split_whitespace()
+        .map(move |tok| (tok.as_ptr() as usize - line.as_ptr() as usize + 1, tok))
+}
+/// Parses the given token as a number or tells where the problem lies
+pub(crate) fn number(line: usize, column: usize, token: &str) -> Result<f64, ParseError> {
+    token.parse::<f64>()
+        .map_err(|_| ParseError::NotANumber{line, column, token: token.to_string()})
+}
+/// Parses the given token as an integer or tells where the problem lies
+fn integer(line: usize, column: usize, token: &str) -> Result<isize, ParseError> {
+    token.parse::<isize>()
+        .map_err(|_| ParseError::NotANumber{line, column, token: token.to_string()})
+}
+/// Counts the number of significant decimal digits in the given token
+fn decimals(token: &str) -> u32 {
+    match token.find('.') {
+        None    => 0,
+        Some(i) => {
+            let digits = token[i+1..].chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>();
+            digits.trim_end_matches('0').len() as u32
+        }
     }
 }
+/// Turns a decimal value into the fixed point representation used internally.
+/// The value is rounded to the nearest unit: truncating it would lose a unit
+/// whenever the decimal value has no exact binary representation (0.29 with 
+/// two decimals gives 28.999...) and whenever the value has more decimals than
+/// the precision.
+pub(crate) fn fixed(x: f64, precision: u32) -> usize {
+    (x * 10_f64.powi(precision as i32)).round() as usize
+}
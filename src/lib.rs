@@ -26,7 +26,18 @@
 //! personal taste.
 
 pub mod instance;
+pub mod solomon;
+pub mod preprocess;
+pub mod precedence;
 pub mod state;
 pub mod model;
 pub mod relax;
 pub mod heuristics;
+pub mod solver;
+pub mod schedule;
+pub mod tour;
+pub mod check;
+pub mod explain;
+pub mod report;
+pub mod bench;
+pub mod compare;
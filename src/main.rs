@@ -20,11 +20,11 @@
 //! This is the main entry point of the program. This is what gets compiled to
 //! the tsptw binary.
 
-use std::{fs::File, path::Path, time::{Duration, Instant}};
+use std::{convert::TryFrom, fs::File, io::{BufRead, BufReader}, path::{Path, PathBuf}, process, sync::{Mutex, atomic::{AtomicUsize, Ordering}}, thread, time::Instant};
 
-use ddo::{Completion, NoDupFrontier, ParallelSolver, Problem, Solution, Solver, TimeBudget, Times, config_builder};
+use ddo::Completion;
 use structopt::StructOpt;
-use tsptw::{instance::TSPTWInstance, model::TSPTW, relax::TSPTWRelax, heuristics::{LoadVarsFromDepth, IncreasingWithDepth}};
+use tsptw::{bench::{instances, instance_name, Output}, compare::{compare, References, ReferenceError, Verdict}, check::{check, CheckError, Evaluation}, explain::{explain as explain_infeasibility, Conflict}, instance::{ParseError, TSPTWInstance}, model::{Objective, TSPTW}, preprocess::Tightening, relax::{Bound, TSPTWRelax}, report::{read_records, Format, Record, Visit}, schedule::schedule, solomon::{Rounding, SolomonReader}, solver::mk_solver, tour::{Tour, TourError}};
 
 /// TSPTW is a solver based on branch-and-bound mdd which solves the travelling
 /// salesman problem with time windows to optimality. 
@@ -36,88 +36,530 @@ enum Args {
     /// This is the action you want to take in order to actually solve an 
     /// instance.
     Solve {
-        /// The path to the TSP+TW instance that needs to be solved.
-        instance: String,
+        #[structopt(flatten)]
+        input: Input,
+        #[structopt(flatten)]
+        settings: Settings,
         /// The verbosity level of what is going to be logged on the console.
         #[structopt(name="verbosity", short, long)]
         verbosity: Option<u8>,
-        /// The maximum width of an mdd layer. The value you provide to this 
-        /// argument will serve as a multiplicator to the default. Hence, 
-        /// providing an argument value `width == 5` for an instance having 20 
-        /// "cities" to visit, means that the maximum layer width will be 100.
-        /// By default, the number of nodes equates to the number of unassigned
-        /// variables.
-        #[structopt(name="width", short, long)]
-        width: Option<usize>,
         /// How many threads do you want to use to solve the problem ?
         #[structopt(name="threads", short, long)]
         threads: Option<usize>,
-        /// How long do you want the solver to keep working on your problem ? 
-        /// (in seconds)
-        #[structopt(name="duration", short, long)]
-        duration: Option<u64>,
+        /// The format of the output: table (for humans), csv or json (one 
+        /// object per line, holding the complete schedule of the tour).
+        #[structopt(name="format", long, default_value="table")]
+        format: Format,
+        /// Shall we print the detailed schedule (arrival, start of service and
+        /// departure at each stop) of the best solution below the table ?
+        #[structopt(name="schedule", long)]
+        schedule: bool,
         /// Shall we print the header in addition to solving the instance ?
         #[structopt(name="header", long)]
-        header: bool
+        header: bool,
+        /// When the instance turns out to have no feasible solution, look 
+        /// for a small set of nodes which cannot be served together (this 
+        /// may require to solve many subsets of the instance).
+        #[structopt(name="explain", long)]
+        explain: bool,
+    },
+    /// Use this command to solve a whole benchmark. The instances are 
+    /// scheduled over several jobs and the record of each instance is
+    /// appended to the output file as soon as it is solved. When the output
+    /// file already exists, the instances it holds a record for are skipped
+    /// (which allows an interrupted campaign to be resumed).
+    Bench {
+        /// The directory holding the instances (they are searched 
+        /// recursively) or a glob pattern matching the instance files.
+        instances: String,
+        #[structopt(flatten)]
+        reader: Reader,
+        #[structopt(flatten)]
+        settings: Settings,
+        /// How many instances are solved simultaneously (by default, one 
+        /// per cpu)
+        #[structopt(name="jobs", short, long)]
+        jobs: Option<usize>,
+        /// How many threads are used to solve each instance ?
+        #[structopt(name="threads", short, long, default_value="1")]
+        threads: usize,
+        /// The file where the records are written
+        #[structopt(name="output", short, long)]
+        output: String,
+        /// The format of the output: table, csv or json
+        #[structopt(name="format", long, default_value="json")]
+        format: Format,
+    },
+    /// Use this command to check the feasibility and evaluate the cost of
+    /// a solution.
+    Check {
+        #[structopt(flatten)]
+        input: Input,
+        /// The path to the solution file. This can either be the output of 
+        /// the solve command or a file holding the permutation of the 
+        /// customers (without the depot).
+        solution: String,
+    },
+    /// Use this command to compare the results of a batch of runs with the 
+    /// best known solutions. Each instance is flagged as an improvement (new
+    /// best solution), a regression (worse value or lost proof of 
+    /// optimality) or a wrong claim (a lower bound above a known solution).
+    /// The command fails when there is a regression or a wrong claim.
+    Compare {
+        /// The files holding the records of the batch (as written by the
+        /// solve or bench commands)
+        results: Vec<String>,
+        /// A file listing the best known solutions (such as sol_langevin.txt)
+        #[structopt(name="reference", short, long)]
+        reference: Vec<String>,
+        /// The records of an earlier batch. Its solutions are considered to
+        /// be known and losing one of its proofs of optimality is a 
+        /// regression.
+        #[structopt(name="baseline", short, long)]
+        baseline: Vec<String>,
+        /// The format of the records: table, csv or json
+        #[structopt(name="format", long, default_value="json")]
+        format: Format,
     },
     /// Use this command if you only intend to print the solution header.
-    PrintHeader
+    PrintHeader {
+        /// The format of the output (table, csv or json)
+        #[structopt(name="format", long, default_value="table")]
+        format: Format,
+    }
+}
+
+// These are the arguments telling which instance to read and how. (A plain
+// comment: structopt would use a doc comment as the about of the commands
+// flattening this struct)
+#[derive(StructOpt)]
+struct Input {
+    /// The path to the TSP+TW instance
+    instance: String,
+    #[structopt(flatten)]
+    reader: Reader,
+}
+impl Input {
+    /// Reads the instance or exits with an error message explaining why 
+    /// that was not possible.
+    fn read_or_exit(&self) -> TSPTWInstance {
+        match self.reader.read(&self.instance) {
+            Ok(inst) => inst,
+            Err(e)   => {
+                eprintln!("{}: {}", self.instance, e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+// These are the arguments telling how to read an instance
+#[derive(StructOpt)]
+struct Reader {
+    /// The number of decimal digits to keep when reading the instance. By
+    /// default, this is the largest number of (significant) decimals 
+    /// found in the instance file.
+    #[structopt(name="precision", short, long)]
+    precision: Option<u32>,
+    /// Read the instance in the coordinates-based format of the Solomon
+    /// VRPTW benchmarks rather than as an explicit distance matrix. In 
+    /// that case, the precision defaults to 4 decimals.
+    #[structopt(name="solomon", long)]
+    solomon: bool,
+    /// How the euclidean distances of a Solomon instance are brought down
+    /// to the desired precision (nearest or truncate)
+    #[structopt(name="rounding", long, default_value="nearest")]
+    rounding: Rounding,
+    /// Keep the service times of a Solomon instance apart instead of
+    /// folding them into the distances.
+    #[structopt(name="separate-service", long)]
+    separate_service: bool,
+    /// Turn the time windows into soft constraints: the service of a node
+    /// may start after the end of its window, at the given cost per unit of
+    /// time. (The nodes listed in the LATENESS section of the instance keep
+    /// their own penalty)
+    #[structopt(name="lateness-penalty", long)]
+    lateness_penalty: Option<usize>,
+    /// How late the service of a node may start at most when the time 
+    /// windows are soft constraints (by default, there is no limit)
+    #[structopt(name="max-lateness", long, requires="lateness-penalty")]
+    max_lateness: Option<f64>,
+    /// The number of vehicles available to serve the customers (this 
+    /// overrides the VEHICLES line of the instance, it is rejected when the
+    /// instance has more shifts or when its tour ends away from the depot)
+    #[structopt(name="vehicles", long)]
+    vehicles: Option<usize>,
+}
+impl Reader {
+    fn read<P: AsRef<Path>>(&self, fname: P) -> Result<TSPTWInstance, ParseError> {
+        let file     = File::open(fname)?;
+        let mut inst = if self.solomon {
+            SolomonReader::new(self.rounding, self.precision.unwrap_or(4))
+                .with_fold_service(!self.separate_service)
+                .read(BufReader::new(file).lines())?
+        } else {
+            TSPTWInstance::try_from(file)?
+        };
+        if let Some(precision) = self.precision {
+            inst.set_precision(precision);
+        }
+        if let Some(penalty) = self.lateness_penalty {
+            inst.soften(penalty, self.max_lateness);
+        }
+        if let Some(fleet) = self.vehicles {
+            inst.set_fleet(fleet)?;
+        }
+        Ok(inst)
+    }
+}
+
+// These are the arguments telling how the solver must be configured
+#[derive(StructOpt)]
+struct Settings {
+    /// The maximum width of an mdd layer. The value you provide to this 
+    /// argument will serve as a multiplicator to the default. Hence, 
+    /// providing an argument value `width == 5` for an instance having 20 
+    /// "cities" to visit, means that the maximum layer width will be 100.
+    /// By default, the number of nodes equates to the number of unassigned
+    /// variables.
+    #[structopt(name="width", short, long)]
+    width: Option<usize>,
+    /// How long do you want the solver to keep working on your problem ? 
+    /// (in seconds)
+    #[structopt(name="duration", short, long)]
+    duration: Option<u64>,
+    /// The quantity to minimize: travel (total travel time), makespan 
+    /// (completion time), travel+waiting (total travel and waiting time) or
+    /// cumulative (sum of the service start times of the customers)
+    #[structopt(name="objective", long, default_value="travel+waiting")]
+    objective: Objective,
+    /// The bound used to estimate the distance that remains to be travelled:
+    /// cheapest-edge or spanning-tree (stronger but more expensive)
+    #[structopt(name="bound", long, default_value="cheapest-edge")]
+    bound: Bound,
+    /// Do not tighten the time windows of the instance before solving it
+    #[structopt(name="no-preprocessing", long)]
+    no_preprocessing: bool,
 }
 
 fn main() -> Result<(), std::io::Error> {
     let args     = Args::from_args();
     match args {
-        Args::PrintHeader => {
-                print_header();
+        Args::PrintHeader{format} => {
+                print_header(format);
         },
-        Args::Solve{instance, verbosity, width, threads, duration, header} => {
-            let inst     = TSPTWInstance::from(File::open(&instance)?);
-            let pb       = TSPTW::new(inst);
-            let relax    = TSPTWRelax::new(&pb);
-            let mut solvr= mk_solver(&pb, relax, verbosity, width, threads, duration);
-
-            let start    = Instant::now();
-            let outcome  = solvr.as_mut().maximize();
-            let finish   = Instant::now();
-
-            let instance = instance_name(&instance);
-            let nb_vars  = pb.nb_vars();
-            let lb       = objective(solvr.as_ref().best_lower_bound());
-            let ub       = objective(solvr.as_ref().best_upper_bound());
-            let solution = solvr.as_ref().best_solution();
-            let duration = finish - start;
+        Args::Solve{input, settings, verbosity, threads, format, schedule: detailed, header, explain} => {
+            let inst     = input.read_or_exit();
+            let pb       = TSPTW::new(inst.clone())
+                .with_preprocessing(!settings.no_preprocessing)
+                .with_objective(settings.objective);
+            if let (Some(t), Some(1..)) = (&pb.tightening, verbosity) {
+                print_tightening(&pb.instance, t);
+            }
+            if let Some(1..) = verbosity {
+                eprintln!("precedences: {} forbidden arcs, {} precedences",
+                          pb.precedences.nb_forbidden_arcs(), pb.precedences.nb_precedences());
+            }
+            let threads  = threads.unwrap_or_else(num_cpus::get);
+            let record   = match solve(instance_name(&input.instance), &pb, &settings, verbosity, threads) {
+                Ok(record) => record,
+                Err(e)     => {
+                    eprintln!("{}: the solver produced an invalid tour ({})", input.instance, e);
+                    process::exit(1);
+                }
+            };
 
             if header {
-                print_header();
+                print_header(format);
+            }
+            println!("{}", record.render(format));
+            if let (Format::Table, Some(tour)) = (format, &record.tour) {
+                if pb.instance.fleet > 1 {
+                    print_routes(tour);
+                }
+                if detailed {
+                    print_schedule(&pb.instance, tour);
+                }
+            }
+            // The conflicts are explained in terms of the original windows
+            if explain && record.tour.is_none() && record.status == "Proved" {
+                print_conflicts(&inst, &explain_infeasibility(&inst, 
+                    |sub| is_feasible(sub, &settings, threads)));
+            }
+        },
+        Args::Bench{instances: pattern, reader, settings, jobs, threads, output, format} => {
+            let files = match instances(&pattern) {
+                Ok(files) => files,
+                Err(e)    => {
+                    eprintln!("{}: {}", pattern, e);
+                    process::exit(1);
+                }
+            };
+            let out = Output::open(&output, format)?;
+            bench(&files, &reader, &settings, threads, jobs.unwrap_or_else(num_cpus::get), out);
+        },
+        Args::Compare{results, reference, baseline, format} => {
+            let mut references = References::default();
+            for fname in reference.iter() {
+                let loaded = File::open(fname)
+                    .map_err(ReferenceError::from)
+                    .and_then(|f| references.load(BufReader::new(f).lines()));
+                if let Err(e) = loaded {
+                    eprintln!("{}: {}", fname, e);
+                    process::exit(1);
+                }
+            }
+            let baseline = read_all(&baseline, format);
+            let records  = read_all(&results, format);
+            let outcome  = compare(&references, &baseline, &records);
+
+            println!("{:40} | {:10} | {:>10} | {:>10} | {:7}", 
+                     "INSTANCE", "STATUS", "VALUE", "BEST KNOWN", "VERDICT");
+            for c in outcome.iter() {
+                println!("{:40} | {:10} | {:>10} | {:>10} | {}",
+                         c.instance, c.status, 
+                         c.value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string()),
+                         c.best_known.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string()),
+                         c.verdict);
+            }
+            let count = |v: Verdict| outcome.iter().filter(|c| c.verdict == v).count();
+            println!("{} instances: {} match, {} improvements, {} worse values, {} lost proofs, {} wrong claims, {} unknown",
+                     outcome.len(),
+                     count(Verdict::Match),
+                     count(Verdict::Improvement),
+                     count(Verdict::WorseValue),
+                     count(Verdict::LostProof),
+                     count(Verdict::WrongClaim),
+                     count(Verdict::Unknown));
+            if outcome.iter().any(|c| c.verdict.is_regression() || c.verdict == Verdict::WrongClaim) {
+                process::exit(1);
+            }
+        },
+        Args::Check{input, solution} => {
+            let inst = input.read_or_exit();
+            let eval = File::open(&solution)
+                .map_err(CheckError::from)
+                .and_then(|f| check(&inst, BufReader::new(f).lines()));
+            match eval {
+                Ok(eval) => print_evaluation(&inst, &eval),
+                Err(e)   => {
+                    eprintln!("{}: {}", solution, e);
+                    process::exit(1);
+                }
             }
-            print_solution(&instance, nb_vars, outcome, &lb, &ub, duration, solution);
         }
     };
     Ok(())
 }
-fn print_header() {
-    println!("{:40} | {:10} | {:10} | {:10} | {:10} | {:8}",
-             "INSTANCE", "STATUS", "UB", "LB", "DURATION", "SOLUTION");
-}
-fn print_solution(name: &str, n: usize, completion: Completion, lb: &str, ub: &str, duration: Duration, solution: Option<Solution>) {
-    println!("{:40} | {:10} | {:10} | {:10} | {:10.3} | {}",
-             name, 
-             status(completion), 
-             lb, ub,
-             duration.as_secs_f32(),
-             solution_to_string(n, solution));
-}
-fn instance_name<P: AsRef<Path>>(fname: P) -> String {
-    let name = fname.as_ref().file_name().unwrap().to_str().unwrap();
-    let bench= fname.as_ref().parent().unwrap().file_name().unwrap().to_str().unwrap();
+fn print_header(format: Format) {
+    if let Some(header) = format.header() {
+        println!("{}", header);
+    }
+}
+fn print_schedule(inst: &TSPTWInstance, route: &[usize]) {
+    println!("{:>8} | {:>12} | {:>12} | {:>12} | {:>12}", 
+             "NODE", "ARRIVAL", "WAIT", "START", "DEPARTURE");
+    for stop in schedule(inst, route) {
+        println!("{:>8} | {:>12} | {:>12} | {:>12} | {:>12}",
+                 stop.node,
+                 inst.format(stop.arrival as isize),
+                 inst.format(stop.waiting() as isize),
+                 inst.format(stop.start as isize),
+                 inst.format(stop.departure as isize));
+    }
+}
+fn print_routes(route: &[usize]) {
+    // The giant route starts and ends at the depot (a tour which ends 
+    // elsewhere is driven by a single vehicle), each vehicle route lies in
+    // between two visits of the depot
+    let routes = route.split(|node| *node == 0).collect::<Vec<_>>();
+    for (k, nodes) in routes[1..routes.len() - 1].iter().enumerate() {
+        let nodes = nodes.iter().map(|x| format!(" {}", x)).collect::<String>();
+        println!("vehicle {}: 0{} 0", k, nodes);
+    }
+}
+fn print_conflicts(inst: &TSPTWInstance, conflicts: &[Conflict]) {
+    for conflict in conflicts.iter() {
+        eprintln!("infeasible: {}", conflict);
+        for node in conflict.nodes() {
+            let tw = inst.timewindows[node];
+            eprintln!("  {:>4}: [{}, {}]", node, inst.format(tw.earliest as isize), inst.format(tw.latest as isize));
+        }
+    }
+}
+fn print_tightening(inst: &TSPTWInstance, t: &Tightening) {
+    if !t.feasible {
+        eprintln!("preprocessing: the instance has no feasible solution");
+        return;
+    }
+    eprintln!("preprocessing: {} windows tightened in {} rounds (total shrinkage {})",
+              t.tightened().len(), t.rounds, inst.format(t.total_shrinkage() as isize));
+    for node in t.tightened() {
+        eprintln!("  {:>4}: [{}, {}] -> [{}, {}]", node,
+                  inst.format(t.before[node].earliest as isize),
+                  inst.format(t.before[node].latest as isize),
+                  inst.format(t.after[node].earliest as isize),
+                  inst.format(t.after[node].latest as isize));
+    }
+}
+fn print_evaluation(inst: &TSPTWInstance, eval: &Evaluation) {
+    let join = |xs: Vec<String>| xs.join(" ");
+    println!("permutation = {}", 
+             join(eval.tour.customers().iter().map(|x| x.to_string()).collect()));
+    if inst.fleet > 1 {
+        print_routes(eval.tour.route());
+    }
+    println!("arrivals = {}", 
+             join(eval.stops.iter().map(|s| inst.format(s.start as isize)).collect()));
+    println!("delays = {}", 
+             join(eval.delays.iter().map(|d| inst.format(*d)).collect()));
+    println!("violated = {}", 
+             join(eval.violations.iter().map(|x| x.to_string()).collect()));
+    if !inst.precedences.is_empty() {
+        println!("inverted = {}", 
+                 join(eval.inversions.iter().map(|(a, b)| format!("{}<{}", a, b)).collect()));
+    }
+    if inst.capacity.is_some() {
+        println!("loads = {}", 
+                 join(eval.loads.iter().map(|x| x.to_string()).collect()));
+        println!("overloaded = {}", 
+                 join(eval.overloads.iter().map(|x| x.to_string()).collect()));
+    }
+    if inst.has_soft_windows() {
+        println!("penalty = {}", inst.format(eval.penalty as isize));
+    }
+    if inst.has_prizes() {
+        println!("skipped = {}", 
+                 join(inst.customers().filter(|i| !eval.tour.route().contains(i)).map(|x| x.to_string()).collect()));
+        println!("forfeited = {}", inst.format(eval.forfeited as isize));
+    }
+    println!("latency = {}", inst.format(eval.latency as isize));
+    println!("makespan = {}\ttourcost = {}\tconstraint violations = {}",
+             inst.format(eval.makespan as isize),
+             inst.format(eval.tour_cost as isize),
+             eval.violations.len() + eval.inversions.len() + eval.overloads.len());
+}
+/// Solves the given problem and reports the outcome. This fails when the
+/// best solution cannot be turned into a valid tour.
+fn solve(instance: String, pb: &TSPTW, settings: &Settings, verbosity: Option<u8>, threads: usize) -> Result<Record, TourError> {
+    let relax    = TSPTWRelax::new(pb).with_bound(settings.bound);
+    let mut solvr= mk_solver(pb, relax, verbosity, settings.width, Some(threads), settings.duration);
+
+    let start    = Instant::now();
+    let outcome  = solvr.as_mut().maximize();
+    let finish   = Instant::now();
 
-    format!("{}/{}", bench, name)
+    let inst     = &pb.instance;
+    let tour     = solvr.as_ref().best_solution()
+        .map(|s| Tour::from_solution(pb, &s))
+        .transpose()?;
+    let ub       = bound(inst, solvr.as_ref().best_lower_bound());
+    let lb       = bound(inst, solvr.as_ref().best_upper_bound());
+    Ok(Record {
+        instance,
+        status     : status(outcome).to_string(),
+        upper_bound: ub,
+        lower_bound: lb,
+        gap        : Record::gap(ub, lb),
+        duration   : (finish - start).as_secs_f64(),
+        threads,
+        width      : settings.width,
+        schedule   : tour.as_ref().map(|t| 
+            schedule(inst, t.route()).iter().map(|s| Visit::new(inst, s)).collect()),
+        tour       : tour.map(|t| t.route().to_vec()),
+        precision  : inst.precision,
+    })
 }
-fn objective(x: isize) -> String {
+/// Tells whether the given instance has a feasible tour (None when that 
+/// could not be decided in the allotted time)
+fn is_feasible(inst: &TSPTWInstance, settings: &Settings, threads: usize) -> Option<bool> {
+    let pb        = TSPTW::new(inst.clone())
+        .with_preprocessing(!settings.no_preprocessing)
+        .with_objective(settings.objective);
+    let relax     = TSPTWRelax::new(&pb).with_bound(settings.bound);
+    let mut solvr = mk_solver(&pb, relax, None, settings.width, Some(threads), settings.duration);
+    let outcome   = solvr.as_mut().maximize();
+    if solvr.as_ref().best_solution().is_some() {
+        Some(true)
+    } else if outcome.is_exact {
+        Some(false)
+    } else {
+        None
+    }
+}
+/// Solves all the given instances using `jobs` worker threads. Each record
+/// is appended to the output as soon as it is available. The instances that
+/// already have a record in the output are skipped, and those which cannot
+/// be read or solved are reported on the standard error.
+fn bench(files: &[PathBuf], reader: &Reader, settings: &Settings, threads: usize, jobs: usize, out: Output) {
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let skip = AtomicUsize::new(0);
+    let out  = Mutex::new(out);
+    thread::scope(|s| {
+        for _ in 0..jobs.max(1) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= files.len() {
+                    break;
+                }
+                let file = &files[i];
+                let name = instance_name(file);
+                let inst = match reader.read(file) {
+                    Ok(inst) => inst,
+                    Err(e)   => {
+                        eprintln!("{}: {}", file.display(), e);
+                        continue;
+                    }
+                };
+                // Whether the record of a previous run still fits can only
+                // be told once the instance is read
+                if out.lock().unwrap().is_done(&name, &inst) {
+                    skip.fetch_add(1, Ordering::SeqCst);
+                    continue;
+                }
+                let pb = TSPTW::new(inst)
+                    .with_preprocessing(!settings.no_preprocessing)
+                    .with_objective(settings.objective);
+                match solve(name, &pb, settings, None, threads) {
+                    Ok(record) => {
+                        let mut out = out.lock().unwrap();
+                        if let Err(e) = out.write(&record) {
+                            eprintln!("{}: cannot write the record ({})", file.display(), e);
+                        } else {
+                            let done = done.fetch_add(1, Ordering::SeqCst) + 1;
+                            eprintln!("[{}/{}] {}", done, files.len(), record.instance);
+                        }
+                    },
+                    Err(e) => 
+                        eprintln!("{}: the solver produced an invalid tour ({})", file.display(), e),
+                }
+            });
+        }
+    });
+    eprintln!("{} instances solved ({} already done)", done.into_inner(), skip.into_inner());
+}
+/// Reads the records of all the given files or exits with an error message
+/// explaining why that was not possible.
+fn read_all(fnames: &[String], format: Format) -> Vec<Record> {
+    let mut records = vec![];
+    for fname in fnames {
+        match File::open(fname).and_then(|f| read_records(BufReader::new(f).lines(), format)) {
+            Ok(mut recs) => records.append(&mut recs),
+            Err(e)       => {
+                eprintln!("{}: {}", fname, e);
+                process::exit(1);
+            }
+        }
+    }
+    records
+}
+/// Converts a bound of the (maximization) solver into a bound of the 
+/// minimization problem. Infinite bounds are None.
+fn bound(inst: &TSPTWInstance, x: isize) -> Option<f64> {
     match x {
-        isize::MIN => "+inf".to_string(),
-        isize::MAX => "-inf".to_string(),
-        _ => format!("{:.2}", -(x as f32 / 10_000.0_f32))
+        isize::MIN | isize::MAX => None,
+        _ => Some(inst.to_decimal(-x))
     }
 }
 fn status(completion: Completion) -> &'static str {
@@ -127,74 +569,3 @@ fn status(completion: Completion) -> &'static str {
        "Timeout"
    }
 }
-fn solution_to_string(nb_vars: usize, solution: Option<Solution>) -> String {
-    match solution {
-        None   => "No feasible solution found".to_string(),
-        Some(s)=> {
-           let mut perm = vec![0; nb_vars];
-           for d in s.iter() {
-               perm[d.variable.id()] = d.value;
-           }
-           let mut txt = String::new();
-           for v in perm {
-                txt = format!("{} {}", txt, v);
-           }
-           txt
-        }
-    }
-}
-
-fn mk_solver<'a>(pb: &'a TSPTW, relax: TSPTWRelax<'a>, 
-                     verbosity: Option<u8>, 
-                     width:     Option<usize>,
-                     threads:   Option<usize>,
-                     duration:  Option<u64>) -> Box<dyn Solver + 'a> {
-    match (&width, &duration) {
-        (Some(w), Some(d)) => {
-            let mdd = config_builder(pb, relax)
-                .with_load_vars(LoadVarsFromDepth::new(pb))
-                .with_max_width(Times(*w, IncreasingWithDepth::new(pb)))
-                .with_cutoff(TimeBudget::new(Duration::from_secs(*d)))
-                .into_deep();
-            let solver = ParallelSolver::new(mdd)
-                .with_verbosity(verbosity.unwrap_or(0))
-                .with_nb_threads(threads.unwrap_or_else(num_cpus::get))
-                .with_frontier(NoDupFrontier::default());
-            Box::new(solver)
-        },
-        (Some(w), None) => {
-            let mdd = config_builder(pb, relax)
-                .with_load_vars(LoadVarsFromDepth::new(pb))
-                .with_max_width(Times(*w, IncreasingWithDepth::new(pb)))
-                .into_deep();
-            let solver = ParallelSolver::new(mdd)
-                .with_verbosity(verbosity.unwrap_or(0))
-                .with_nb_threads(threads.unwrap_or_else(num_cpus::get))
-                .with_frontier(NoDupFrontier::default());
-            Box::new(solver)
-        },
-        (None, Some(d)) => {
-            let mdd = config_builder(pb, relax)
-                .with_load_vars(LoadVarsFromDepth::new(pb))
-                .with_max_width(IncreasingWithDepth::new(pb))
-                .with_cutoff(TimeBudget::new(Duration::from_secs(*d)))
-                .into_deep();
-            let solver = ParallelSolver::new(mdd)
-                .with_verbosity(verbosity.unwrap_or(0))
-                .with_nb_threads(threads.unwrap_or_else(num_cpus::get))
-                .with_frontier(NoDupFrontier::default());
-            Box::new(solver)
-        },
-        (None, None) => {
-            let mdd = config_builder(pb, relax)
-                .with_load_vars(LoadVarsFromDepth::new(pb))
-                .with_max_width(IncreasingWithDepth::new(pb))
-                .into_deep();
-            let solver = ParallelSolver::new(mdd)
-                .with_verbosity(verbosity.unwrap_or(0))
-                .with_nb_threads(threads.unwrap_or_else(num_cpus::get))
-                .with_frontier(NoDupFrontier::default());
-            Box::new(solver)
-        }
-    }
-}
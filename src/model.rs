@@ -20,31 +20,130 @@
 //! This module contains the definition of the dynamic programming formulation 
 //! of the TSP+TW. (Implementation of the `Problem` trait).
 
-use std::ops::Not;
+use std::{ops::Not, str::FromStr};
 
 use bitset_fixed::BitSet;
-use ddo::{BitSetIter, Domain, Problem};
+use ddo::{BitSetIter, Domain, Problem, Variable};
 
-use crate::{instance::TSPTWInstance, state::{ElapsedTime, Position, State}};
+use crate::{instance::{End, TSPTWInstance}, precedence::PrecedenceGraph, preprocess::{tighten, Tightening}, state::{ElapsedTime, Load, Position, State, Vehicle}};
 
 
+/// This enumeration lists the quantities that can be minimized
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Objective {
+    /// The total distance travelled along the tour
+    TravelTime,
+    /// The time at which the salesman is back at the depot (completion time).
+    /// When there are several vehicles, this is the total duration of their
+    /// routes (from the start of their shift until they are back).
+    Makespan,
+    /// The total distance travelled plus the total time spent waiting for 
+    /// the time windows to open
+    #[default]
+    TravelAndWaiting,
+    /// The (weighted) sum of the times at which the service of the customers
+    /// starts: the time each customer waits for the salesman
+    Cumulative,
+}
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "travel"                            => Ok(Objective::TravelTime),
+            "makespan"                          => Ok(Objective::Makespan),
+            "travel+waiting" | "travel-waiting" => Ok(Objective::TravelAndWaiting),
+            "cumulative"                        => Ok(Objective::Cumulative),
+            _ => Err(format!("unknown objective '{}' (expected travel, makespan, travel+waiting or cumulative)", s))
+        }
+    }
+}
+
 /// This is the structure encapsulating the TSPTW problem.
 #[derive(Clone)]
 pub struct TSPTW {
-    pub instance: TSPTWInstance,
-    pub initial : State,
+    pub instance : TSPTWInstance,
+    pub initial  : State,
+    pub objective: Objective,
+    /// The outcome of the time window tightening (if it was performed)
+    pub tightening: Option<Tightening>,
+    /// The forbidden arcs and precedences implied by the time windows
+    pub precedences: PrecedenceGraph,
 }
 impl TSPTW {
-    pub fn new(inst: TSPTWInstance) -> Self {
+    /// Creates the problem. The time windows of the instance are tightened
+    /// unless this is disabled with `with_preprocessing`.
+    pub fn new(mut inst: TSPTWInstance) -> Self {
+        let tightening = Some(tighten(&mut inst));
         let mut state = State {
             position  : Position::Node(0),
-            elapsed   : ElapsedTime::FixedAmount{duration: 0},
+            elapsed   : ElapsedTime::FixedAmount{duration: inst.shift(0).earliest},
+            load      : Load::FixedLoad{amount: inst.loads[0].max(0) as usize},
+            vehicle   : Vehicle::FixedVehicle{index: 0},
             must_visit: BitSet::new(inst.nb_nodes as usize).not(),
             maybe_visit: None,
             depth : 0
         };
         state.must_visit.set(0, false);
-        Self { instance: inst, initial: state }
+        if let End::Node(end) = inst.end {
+            state.must_visit.set(end, false);
+        }
+        // The customers which have a prize may be visited but need not be
+        if inst.has_prizes() {
+            let mut optional = BitSet::new(inst.nb_nodes as usize);
+            for i in inst.customers().filter(|i| inst.is_optional(*i)) {
+                state.must_visit.set(i, false);
+                optional.set(i, true);
+            }
+            state.maybe_visit = Some(optional);
+        }
+        let precedences = PrecedenceGraph::new(&inst);
+        Self { instance: inst, initial: state, objective: Objective::default(), tightening, precedences }
+    }
+    /// Enables or disables the time window tightening. When it is disabled,
+    /// the original time windows of the instance are restored.
+    pub fn with_preprocessing(mut self, enabled: bool) -> Self {
+        match (enabled, self.tightening.take()) {
+            (true,  None)    => self.tightening = Some(tighten(&mut self.instance)),
+            (true,  t)       => self.tightening = t,
+            (false, Some(t)) => self.instance.timewindows = t.before,
+            (false, None)    => (),
+        }
+        self.precedences = PrecedenceGraph::new(&self.instance);
+        self
+    }
+    /// Changes the quantity which is being minimized
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+    /// Returns the variable which decides what node is visited at the given
+    /// depth in the tour. The transition relation itself does not care 
+    /// about the identity of the variables; this mapping is the contract 
+    /// that ties the variable ordering heuristic to the interpretation of 
+    /// the solutions.
+    pub fn variable_at(depth: usize) -> Variable {
+        Variable(depth)
+    }
+    /// Returns the depth in the tour which is decided by the given variable
+    /// (this is the inverse of `variable_at`)
+    pub fn depth_of(var: Variable) -> usize {
+        var.id()
+    }
+    /// Returns the node reached by the last decision of the tour. When the 
+    /// tour has a free end, this last decision is a return to the depot 
+    /// which costs nothing (and which is not part of the tour).
+    pub fn end_node(&self) -> usize {
+        match self.instance.end {
+            End::Node(node) => node,
+            _               => 0,
+        }
+    }
+    /// Returns true iff the tour is over: the salesman has reached the end 
+    /// of the tour before the last layer (he left out some optional 
+    /// customers) and he stays there for the remaining decisions.
+    pub fn is_over(&self, state: &State) -> bool {
+        state.vehicle.first() >= self.instance.fleet
     }
 }
 
@@ -55,49 +154,71 @@ const TO_DEPOT    : [isize;1]       = [0];
 const GO_TO_DEPOT : Domain<'static> = Domain::Slice(&TO_DEPOT);
 
 impl Problem<State> for TSPTW {
+    /// One decision per customer and one per return to the depot (or move
+    /// to the end of the tour)
     fn nb_vars(&self) -> usize {
-        self.instance.nb_nodes as usize
+        self.instance.nb_customers() + self.instance.fleet
     }
 
     fn initial_state(&self) -> State {
         self.initial.clone()
     }
 
+    /// The prizes are collected along the tour: it starts with the prizes
+    /// of all the customers as a debt, so that the prizes of the customers
+    /// which are left out are part of the cost of the tour.
     fn initial_value(&self) -> isize {
-        0
+        -(self.instance.total_prize() as isize)
     }
     
     fn domain_of<'a>(&self, state: &'a State, _var: ddo::Variable) -> ddo::Domain<'a> {
+        // Once the tour is over, the salesman stays where it ended
+        if self.is_over(state) {
+            return self.end_domain();
+        }
         // When we are at the end of the tour, the only possible destination is
-        // to go back to the depot. Any state that violates this constraint is
-        // de facto infeasible.
+        // to go back to the depot (or to the end node). Any state that 
+        // violates this constraint is de facto infeasible.
         if state.depth as usize == self.nb_vars() - 1 {
-            if self.can_move_to(state, 0){
-                return GO_TO_DEPOT;
-            } else {
-                return EMPTY_DOMAIN;
-            }
+            return if self.can_end(state) { self.end_domain() } else { EMPTY_DOMAIN };
         }
 
 
+        // A node that must still be visited but can no longer be reached in
+        // time makes the state infeasible (unless another vehicle can still
+        // leave the depot to serve it). Those nodes which cannot be visited 
+        // right now (but maybe later) are simply skipped.
+        let spare_vehicle  = self.has_spare_vehicle(state);
         let mut domain     = vec![];
         for i in BitSetIter::new(&state.must_visit) {
-            if self.can_move_to(state, i) {
-                domain.push(i as isize);
-            } else {
+            if !self.can_reach(state, i) || !self.can_follow_predecessors(state, i) {
+                if spare_vehicle {
+                    continue;
+                }
                 return EMPTY_DOMAIN;
             }
+            if self.can_move_to(state, i) && self.can_be_next(state, i) && self.can_carry(state, i) {
+                domain.push(i as isize);
+            }
         }
 
         // Add those that can possibly be visited
         if let Some(maybe_visit) = &state.maybe_visit {
             for i in BitSetIter::new(maybe_visit) {
-                if self.can_move_to(state, i) {
+                if self.can_move_to(state, i) && self.can_be_next(state, i) && self.can_carry(state, i) {
                     domain.push(i as isize);
                 }
             }
         }
 
+        // The current vehicle may return to the depot and let the next one
+        // take over. The tour may also end early once every customer which 
+        // is not optional has been served.
+        let may_end = self.instance.has_prizes() && state.must_visit.count_ones() == 0;
+        if (spare_vehicle || may_end) && self.can_end(state) {
+            domain.push(self.end_node() as isize);
+        }
+
         Domain::from(domain)
     }
 
@@ -111,11 +232,28 @@ impl Problem<State> for TSPTW {
             maybe.set(d.value as usize, false);
         }
 
-        let time = self.arrival_time(state, d.value as usize);
+        let j    = d.value as usize;
+        let last = state.depth as usize == self.nb_vars() - 1;
+        let (time, load, vehicle) = if self.is_over(state) {
+            (state.elapsed, state.load, state.vehicle)
+        } else if j == self.end_node() && !last && self.has_spare_vehicle(state) {
+            // The next vehicle leaves the depot when its shift starts
+            let vehicle = state.vehicle.next(self.instance.fleet);
+            (self.shift_start(vehicle), self.initial.load, vehicle)
+        } else if j == self.end_node() && !last {
+            // The tour ends early: the optional customers which have not been
+            // visited are left out
+            maybes = None;
+            (self.arrival_time(state, j), state.load, Vehicle::FixedVehicle{index: self.instance.fleet})
+        } else {
+            (self.arrival_time(state, j), self.load_after(state, j), state.vehicle)
+        };
 
         State {
             position : Position::Node(d.value as u16),
             elapsed  : time,
+            load,
+            vehicle,
             must_visit: remaining,
             maybe_visit: maybes,
             depth: state.depth + 1
@@ -126,48 +264,177 @@ impl Problem<State> for TSPTW {
         // TSPTW is a minimization problem but the solver works with a 
         // maximization perspective. So we have to negate the min if we want to
         // yield a lower bound.
-        let twj = self.instance.timewindows[d.value as usize];
-        let travel_time = self.min_distance_to(state, d.value as usize);
-        let waiting_time = match state.elapsed {
-            ElapsedTime::FixedAmount{duration} => 
-                if (duration + travel_time) < twj.earliest {
-                    twj.earliest - (duration + travel_time)
-                } else {
-                    0
-                },
-            ElapsedTime::FuzzyAmount{earliest, ..} => 
-                if (earliest + travel_time) < twj.earliest {
-                    twj.earliest - (earliest + travel_time)
-                } else {
-                    0
-                }
+        //
+        // Note: the service times are not part of the travel cost (they are
+        // a constant anyway), but they do delay the arrival at the next node
+        // hence they reduce the waiting time. In the case of a relaxed state,
+        // we must thus assume we left as late as possible.
+        //
+        // When j may be served late, the penalty is charged for the earliest
+        // time at which the salesman may start serving j.
+        //
+        // A vehicle which goes straight back to the depot is simply not used
+        // and the salesman stays where he is once the tour is over. When the
+        // tour has a free end, the salesman stops as soon as he is done with
+        // the last customer.
+        //
+        // The prize of j (if any) is collected when j is visited.
+        let j    = d.value as usize;
+        let inst = &self.instance;
+        if j == self.end_node() && self.may_be_at(state, j) {
+            return 0;
+        }
+        if inst.end == End::Free && j == 0 {
+            return match self.objective {
+                Objective::Makespan => -(self.min_service_at(state) as isize),
+                _                   => 0,
+            };
+        }
+        let travel_time  = self.min_distance_to(state, j);
+        let earliest_arr = self.earliest_arrival(state, j);
+        let penalty      = inst.lateness_cost(j, earliest_arr);
+        let cost = match self.objective {
+            Objective::TravelTime => 
+                travel_time,
+            Objective::TravelAndWaiting => {
+                let latest_arr   = state.elapsed.latest() + self.max_service_at(state) + travel_time;
+                let waiting_time = inst.min_waiting(j, earliest_arr, latest_arr.max(earliest_arr));
+                travel_time + waiting_time
+            },
+            Objective::Makespan => {
+                // This is the time between the starts of service at the 
+                // current position and at j.
+                let duration     = self.min_duration_to(state, j);
+                let waiting_time = inst.min_waiting(j, earliest_arr, state.elapsed.latest() + duration);
+                duration + waiting_time
+            },
+            Objective::Cumulative if inst.is_customer(j) => {
+                let start = inst.next_opening(j, earliest_arr).unwrap_or(earliest_arr);
+                inst.weights[j] * start
+            },
+            Objective::Cumulative => 0,
         };
-
-        -( (travel_time + waiting_time) as isize)
+        inst.prize(j) as isize - (cost + penalty) as isize
     }
 }
 
 impl TSPTW {
     pub fn can_move_to(&self, state: &State, j: usize) -> bool {
-        let twj         = self.instance.timewindows[j];
-        let min_arrival = state.elapsed.add_duration(self.min_distance_to(state, j));
-        match min_arrival {
-            ElapsedTime::FixedAmount{duration}     => duration <= twj.latest,
-            ElapsedTime::FuzzyAmount{earliest, ..} => earliest <= twj.latest,
+        if j == self.end_node() && self.may_be_at(state, j) {
+            return true;
+        }
+        match self.instance.next_opening(j, self.earliest_arrival(state, j)) {
+            None        => false,
+            Some(start) => j != 0 || start <= self.latest_return(state),
+        }
+    }
+    /// Returns true iff node j can still be reached in time, possibly going
+    /// through other nodes first. When the travel times vary, a detour may
+    /// be quicker than the direct arc (the latter might be jammed).
+    fn can_reach(&self, state: &State, j: usize) -> bool {
+        self.instance.next_opening(j, self.earliest_reach(state, j)).is_some()
+    }
+    /// Returns true iff another vehicle may still leave the depot after the
+    /// current one
+    fn has_spare_vehicle(&self, state: &State) -> bool {
+        state.vehicle.first() + 1 < self.instance.fleet
+    }
+    /// Returns true iff the salesman can reach the end of the tour in time
+    fn can_end(&self, state: &State) -> bool {
+        match self.instance.end {
+            End::Free => true,
+            _         => self.can_move_to(state, self.end_node()),
+        }
+    }
+    /// The decision which moves to the end of the tour (when the tour has a
+    /// free end, this is a return to the depot which is not part of it)
+    fn end_domain(&self) -> Domain<'static> {
+        match self.instance.end {
+            End::Node(end) => Domain::from(vec![end as isize]),
+            _              => GO_TO_DEPOT,
+        }
+    }
+    /// Returns true iff the current position might be the given node. When
+    /// that node is the depot, the current vehicle might not have left it
+    /// yet; when it is the end of the tour, the tour might be over.
+    fn may_be_at(&self, state: &State, node: usize) -> bool {
+        match &state.position {
+            Position::Node(i)             => *i as usize == node,
+            Position::Virtual(candidates) => candidates[node],
+        }
+    }
+    /// The latest time at which the current vehicle may be back at the depot
+    fn latest_return(&self, state: &State) -> usize {
+        (state.vehicle.first()..=state.vehicle.last())
+            .map(|v| self.instance.shift(v).latest)
+            .max()
+            .unwrap()
+    }
+    /// The time at which the given vehicle leaves the depot
+    fn shift_start(&self, vehicle: Vehicle) -> ElapsedTime {
+        let starts   = (vehicle.first()..=vehicle.last()).map(|v| self.instance.shift(v).earliest);
+        let earliest = starts.clone().min().unwrap();
+        let latest   = starts.max().unwrap();
+        if earliest == latest {
+            ElapsedTime::FixedAmount{duration: earliest}
+        } else {
+            ElapsedTime::FuzzyAmount{earliest, latest}
+        }
+    }
+    /// Returns true iff node j may be the next node of the tour: the arc 
+    /// leading to j is not forbidden (for all the nodes where the salesman 
+    /// might be) and every node that must precede j has already been visited.
+    /// 
+    /// Note: in a relaxed state, `must_visit` only holds the nodes that all
+    /// merged states have yet to visit. Hence, j cannot be the next node of 
+    /// any of these states if one of its predecessors belongs to that set.
+    fn can_be_next(&self, state: &State, j: usize) -> bool {
+        let graph     = &self.precedences;
+        let reachable = match &state.position {
+            Position::Node(i) => !graph.is_forbidden(*i as usize, j),
+            Position::Virtual(candidates) => 
+                BitSetIter::new(candidates).any(|i| !graph.is_forbidden(i, j))
+        };
+        reachable && graph.predecessors(j).buffer().iter()
+            .zip(state.must_visit.buffer().iter())
+            .all(|(p, m)| p & m == 0)
+    }
+    /// Returns false when node j can no longer be reached in time because 
+    /// some node which must precede j has yet to be visited: the salesman
+    /// would arrive at j too late even if he reached that predecessor as 
+    /// soon as possible and then took the quickest way to j.
+    fn can_follow_predecessors(&self, state: &State, j: usize) -> bool {
+        let graph  = &self.precedences;
+        let latest = self.instance.deadline(j);
+        BitSetIter::new(graph.predecessors(j))
+            .filter(|p| state.must_visit[*p])
+            .all(|p| {
+                let arrival = self.earliest_reach(state, p);
+                let start   = arrival.max(self.instance.timewindows[p].earliest);
+                start + graph.shortest(p, j) <= latest
+            })
+    }
+    /// Returns true iff the load of the vehicle would remain within its 
+    /// capacity (and non negative) if node j was visited next
+    fn can_carry(&self, state: &State, j: usize) -> bool {
+        match self.instance.capacity {
+            None           => true,
+            Some(capacity) => j == 0 || state.load.can_change_by(self.instance.loads[j], capacity),
+        }
+    }
+    /// Returns the load of the vehicle when it leaves node j (which is 
+    /// visited right after the current position). The load is left as it is
+    /// when it is not constrained and when the vehicle reaches the depot or
+    /// the end of the tour.
+    fn load_after(&self, state: &State, j: usize) -> Load {
+        match self.instance.capacity {
+            Some(capacity) if self.instance.is_customer(j) => state.load.change_by(self.instance.loads[j], capacity),
+            _                        => state.load,
         }
     }
     fn arrival_time(&self, state: &State, j: usize) -> ElapsedTime {
-       let min_arrival = state.elapsed.add_duration(self.min_distance_to(state, j));
-       let max_arrival = state.elapsed.add_duration(self.max_distance_to(state, j));
-
-       let min_arrival = match min_arrival {
-           ElapsedTime::FixedAmount{duration}     => duration,
-           ElapsedTime::FuzzyAmount{earliest, ..} => earliest
-       };
-       let max_arrival = match max_arrival {
-           ElapsedTime::FixedAmount{duration}    => duration,
-           ElapsedTime::FuzzyAmount{latest, ..}  => latest
-       };
+       let min_arrival = self.earliest_arrival(state, j);
+       let max_arrival = self.latest_arrival(state, j);
        // This would be the arrival time if we never had to wait.
        let arrival_time = 
            if min_arrival.eq(&max_arrival) { 
@@ -176,15 +443,16 @@ impl TSPTW {
                ElapsedTime::FuzzyAmount{earliest: min_arrival, latest: max_arrival}
            };
        // In order to account for the possible waiting time, we need to adjust
-       // the earliest arrival time
-       let twj = self.instance.timewindows[j];
+       // the arrival times to the next opening of j
+       let twj  = self.instance.timewindows[j];
+       let open = |time: usize| self.instance.next_opening(j, time);
        match arrival_time {
           ElapsedTime::FixedAmount{duration} => {
-              ElapsedTime::FixedAmount{duration: duration.max(twj.earliest)}
+              ElapsedTime::FixedAmount{duration: open(duration).unwrap_or_else(|| duration.max(twj.earliest))}
           },
-          ElapsedTime::FuzzyAmount{mut earliest, mut latest} => {
-            earliest = earliest.max(twj.earliest);
-            latest   = latest.min(twj.latest);
+          ElapsedTime::FuzzyAmount{earliest, latest} => {
+            let earliest = open(earliest).unwrap_or_else(|| earliest.max(twj.earliest));
+            let latest   = open(latest).unwrap_or_else(|| self.instance.deadline(j)).max(earliest);
 
             if earliest.eq(&latest) {
                 ElapsedTime::FixedAmount{duration: earliest}
@@ -194,24 +462,103 @@ impl TSPTW {
           },
       }
     }
+    /// Returns the shortest time it may take to travel from the current
+    /// position to node j (the salesman leaves as soon as he is done with 
+    /// the service at his current position)
     fn min_distance_to(&self, state: &State, j: usize) -> usize {
         match &state.position {
-            Position::Node(i) => self.instance.distances[(*i as usize, j)],
+            Position::Node(i) => self.travel_to(state, *i as usize, j),
+            Position::Virtual(candidates) => 
+                BitSetIter::new(candidates)
+                    .map(|i| self.travel_to(state, i, j))
+                    .min()
+                    .unwrap()
+        }
+    }
+    /// Returns the minimum duration between the start of the service at the
+    /// current position and the arrival at node j (service + travel)
+    fn min_duration_to(&self, state: &State, j: usize) -> usize {
+        match &state.position {
+            Position::Node(i) => self.duration(state, *i as usize, j),
+            Position::Virtual(candidates) => 
+                BitSetIter::new(candidates)
+                    .map(|i| self.duration(state, i, j))
+                    .min()
+                    .unwrap()
+        }
+    }
+    /// Returns the earliest time at which the salesman may reach node j. 
+    /// Since leaving later never means arriving earlier, this is when he 
+    /// leaves as early as possible.
+    fn earliest_arrival(&self, state: &State, j: usize) -> usize {
+        let arrival = |i: usize| {
+            let departure = state.elapsed.earliest() + self.instance.service_times[i];
+            departure + self.instance.travel_time(i, j, departure)
+        };
+        match &state.position {
+            Position::Node(i) => arrival(*i as usize),
+            Position::Virtual(candidates) => BitSetIter::new(candidates).map(arrival).min().unwrap()
+        }
+    }
+    /// Returns a lower bound on the time at which the salesman may reach 
+    /// node j, whatever the nodes he visits on the way
+    fn earliest_reach(&self, state: &State, j: usize) -> usize {
+        let graph    = &self.precedences;
+        let shortest = match &state.position {
+            Position::Node(i) => graph.shortest(*i as usize, j),
             Position::Virtual(candidates) => 
                 BitSetIter::new(candidates)
-                    .map(|i| self.instance.distances[(i as usize, j as usize)])
+                    .map(|i| graph.shortest(i, j))
+                    .min()
+                    .unwrap()
+        };
+        state.elapsed.earliest() + shortest
+    }
+    /// Returns the latest time at which the salesman may reach node j, that
+    /// is when he leaves as late as possible
+    fn latest_arrival(&self, state: &State, j: usize) -> usize {
+        let arrival = |i: usize| {
+            let departure = state.elapsed.latest() + self.instance.service_times[i];
+            departure + self.instance.travel_time(i, j, departure)
+        };
+        match &state.position {
+            Position::Node(i) => arrival(*i as usize),
+            Position::Virtual(candidates) => BitSetIter::new(candidates).map(arrival).max().unwrap()
+        }
+    }
+    /// Returns the shortest service time the salesman might be spending at
+    /// his current position
+    fn min_service_at(&self, state: &State) -> usize {
+        match &state.position {
+            Position::Node(i) => self.instance.service_times[*i as usize],
+            Position::Virtual(candidates) => 
+                BitSetIter::new(candidates)
+                    .map(|i| self.instance.service_times[i])
                     .min()
                     .unwrap()
         }
     }
-    fn max_distance_to(&self, state: &State, j: usize) -> usize {
+    /// Returns the longest service time the salesman might be spending at
+    /// his current position
+    fn max_service_at(&self, state: &State) -> usize {
         match &state.position {
-            Position::Node(i) => self.instance.distances[(*i as usize, j)],
+            Position::Node(i) => self.instance.service_times[*i as usize],
             Position::Virtual(candidates) => 
                 BitSetIter::new(candidates)
-                    .map(|i| self.instance.distances[(i as usize, j as usize)])
+                    .map(|i| self.instance.service_times[i])
                     .max()
                     .unwrap()
         }
     }
+    /// The shortest time it may take to travel from node i to node j when 
+    /// the service of node i starts at some time of the current state
+    fn travel_to(&self, state: &State, i: usize, j: usize) -> usize {
+        let service = self.instance.service_times[i];
+        self.instance.min_travel_time(i, j, state.elapsed.earliest() + service, state.elapsed.latest() + service)
+    }
+    /// The shortest time it may take to serve node i and then travel to 
+    /// node j
+    fn duration(&self, state: &State, i: usize, j: usize) -> usize {
+        self.instance.service_times[i] + self.travel_to(state, i, j)
+    }
 }
@@ -0,0 +1,129 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module derives a precedence graph from the time windows of an 
+//! instance. Two kinds of relations are identified:
+//!
+//! * forbidden arcs: node j can never be visited right after node i because
+//!   the service of j could not start in time even if the service of i 
+//!   started as early as possible;
+//! * precedences: node j must be visited before node i because even the
+//!   quickest way to go from i to j (possibly through other nodes) would 
+//!   reach j too late.
+//!
+//! Precedences are derived from the shortest travel times between the nodes
+//! (rather than from the direct distances) so that they remain valid when 
+//! the distances do not satisfy the triangle inequality. The precedences 
+//! which are explicitly given by the instance are added to the graph too.
+
+use bitset_fixed::BitSet;
+use ddo::Matrix;
+
+use crate::instance::TSPTWInstance;
+
+/// The forbidden arcs and precedences implied by the time windows
+#[derive(Clone)]
+pub struct PrecedenceGraph {
+    /// The shortest time between the start of the service at a node and the
+    /// arrival at another node
+    shortest    : Matrix<usize>,
+    /// For each node i, the set of nodes that cannot be visited right after i
+    forbidden   : Vec<BitSet>,
+    /// For each node j, the set of nodes that must be visited before j
+    predecessors: Vec<BitSet>,
+}
+impl PrecedenceGraph {
+    /// Derives the precedence graph of the given instance
+    pub fn new(inst: &TSPTWInstance) -> Self {
+        let n  = inst.nb_nodes as usize;
+        let tw = &inst.timewindows;
+
+        // Floyd-Warshall on the travel times (service at the origin included)
+        let mut shortest = Matrix::new_default(n, n, 0);
+        for i in 0..n {
+            for j in 0..n {
+                shortest[(i, j)] = if i == j { 0 } else { inst.service_times[i] + inst.distances[(i, j)] };
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    let via_k = shortest[(i, k)] + shortest[(k, j)];
+                    if via_k < shortest[(i, j)] {
+                        shortest[(i, j)] = via_k;
+                    }
+                }
+            }
+        }
+
+        // The tour leaves the depot at time zero (the window of the depot 
+        // constrains the return)
+        let earliest = |i: usize| if i == 0 { 0 } else { tw[i].earliest };
+
+        let mut forbidden    = vec![BitSet::new(n); n];
+        let mut predecessors = vec![BitSet::new(n); n];
+        for i in 0..n {
+            for j in (0..n).filter(|j| *j != i) {
+                let direct = earliest(i) + inst.service_times[i] + inst.distances[(i, j)];
+                if direct > inst.deadline(j) {
+                    forbidden[i].set(j, true);
+                }
+                // The depot (and the end node) is neither preceded nor
+                // followed by any customer. When there are several vehicles,
+                // j might be served by another vehicle than i: no precedence
+                // can be derived.
+                if inst.fleet == 1 && inst.is_customer(i) && inst.is_customer(j) && earliest(i) + shortest[(i, j)] > inst.deadline(j) {
+                    predecessors[i].set(j, true);
+                }
+            }
+        }
+
+        for &(before, after) in inst.precedences.iter() {
+            forbidden[after].set(before, true);
+            predecessors[after].set(before, true);
+        }
+
+        Self { shortest, forbidden, predecessors }
+    }
+    /// Returns true iff node j can never be visited right after node i
+    pub fn is_forbidden(&self, i: usize, j: usize) -> bool {
+        self.forbidden[i][j]
+    }
+    /// Returns true iff node i must be visited before node j
+    pub fn must_precede(&self, i: usize, j: usize) -> bool {
+        self.predecessors[j][i]
+    }
+    /// The set of nodes that must be visited before node j
+    pub fn predecessors(&self, j: usize) -> &BitSet {
+        &self.predecessors[j]
+    }
+    /// The shortest time between the start of the service at node i and the
+    /// arrival at node j
+    pub fn shortest(&self, i: usize, j: usize) -> usize {
+        self.shortest[(i, j)]
+    }
+    /// The number of forbidden arcs
+    pub fn nb_forbidden_arcs(&self) -> usize {
+        self.forbidden.iter().map(|f| f.count_ones() as usize).sum()
+    }
+    /// The number of precedence relations
+    pub fn nb_precedences(&self) -> usize {
+        self.predecessors.iter().map(|p| p.count_ones() as usize).sum()
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module implements the classic time window tightening preprocessing
+//! of the TSPTW (Desrochers, Desrosiers and Solomon, 1992). The windows are
+//! narrowed by reasoning on the feasible predecessors and successors of each
+//! node, until a fixpoint is reached:
+//!
+//! * the service of a node cannot start before the earliest time at which
+//!   it can be reached from one of its feasible predecessors;
+//! * the service of a node cannot start so late that none of its feasible
+//!   successors can be reached in time;
+//! * the service of a node cannot start later than the latest time at which
+//!   it can be reached from one of its feasible predecessors (unless its 
+//!   window only opens after that time).
+//!
+//! When a node has several time windows, the waiting for the next window to
+//! open is accounted for in the first and last rules.
+//!
+//! An arc (i, j) is feasible iff the service of j can start in time when the
+//! service of i starts as early as possible. The depot plays two roles: it 
+//! is the predecessor of the first customer (the tour starts at time zero)
+//! and the successor of the last one (where its time window applies).
+//!
+//! These rules never remove a feasible schedule: the start of service at
+//! each node of a feasible tour is the same with the original and with the
+//! tightened windows.
+
+use crate::instance::{End, TSPTWInstance, TimeWindow};
+
+/// This is the outcome of the tightening of the time windows of an instance
+#[derive(Debug, Clone)]
+pub struct Tightening {
+    /// The time windows before the preprocessing
+    pub before  : Vec<TimeWindow>,
+    /// The time windows after the preprocessing
+    pub after   : Vec<TimeWindow>,
+    /// The number of rounds it took to reach the fixpoint
+    pub rounds  : usize,
+    /// This is false when the preprocessing proved that the instance has no
+    /// feasible solution (some window became empty). In that case, the 
+    /// windows of the instance are left untouched.
+    pub feasible: bool,
+}
+impl Tightening {
+    /// How much the window of the given node shrank
+    pub fn shrinkage(&self, node: usize) -> usize {
+        width(self.before[node]) - width(self.after[node])
+    }
+    /// How much all the windows shrank (in total)
+    pub fn total_shrinkage(&self) -> usize {
+        (0..self.before.len()).map(|i| self.shrinkage(i)).sum()
+    }
+    /// The nodes whose window shrank
+    pub fn tightened(&self) -> Vec<usize> {
+        (0..self.before.len()).filter(|i| self.shrinkage(*i) > 0).collect()
+    }
+}
+fn width(tw: TimeWindow) -> usize {
+    tw.latest.saturating_sub(tw.earliest)
+}
+
+/// Tightens the time windows of the given instance and reports how much
+/// each of them shrank.
+pub fn tighten(inst: &mut TSPTWInstance) -> Tightening {
+    let before       = inst.timewindows.clone();
+    let mut tw       = before.clone();
+    let mut rounds   = 0;
+    let mut feasible = true;
+
+    // The rules below all assume that the end of a window is a hard deadline
+    // and that the vehicles leave the depot at time zero to come back to it:
+    // the windows are left untouched as soon as some node may be served 
+    // late, some vehicle has a shift or the tour ends elsewhere. They also
+    // assume that every customer is served (an optional customer whose
+    // window becomes empty is merely left out) and that the travel times do
+    // not depend on the departure time.
+    let mut changed  = tw.len() > 1 && !inst.has_soft_windows() && inst.shifts.is_empty() && inst.end == End::Depot 
+        && !inst.has_prizes() && !inst.has_travel_functions();
+    while changed && feasible {
+        changed = false;
+        rounds += 1;
+        for k in 0..tw.len() {
+            match narrow(inst, &tw, k) {
+                None => {
+                    feasible = false;
+                    break;
+                },
+                Some(window) => 
+                    if window.earliest != tw[k].earliest || window.latest != tw[k].latest {
+                        tw[k]   = window;
+                        changed = true;
+                    }
+            }
+        }
+    }
+
+    if feasible {
+        inst.timewindows = tw.clone();
+    } else {
+        tw = before.clone();
+    }
+    Tightening { before, after: tw, rounds, feasible }
+}
+
+/// Applies the tightening rules to the window of node k (which stands for
+/// the return to the depot when k is zero). This returns None when the 
+/// window becomes empty.
+fn narrow(inst: &TSPTWInstance, tw: &[TimeWindow], k: usize) -> Option<TimeWindow> {
+    let n      = tw.len();
+    let travel = |i: usize, j: usize| inst.service_times[i] + inst.distances[(i, j)];
+
+    // The earliest and latest times at which k can be reached from one of
+    // its feasible predecessors. When k is a customer, the depot is one of 
+    // these predecessors and it is left at time zero.
+    let mut earliest_arrival = None;
+    let mut latest_arrival   = 0;
+    for i in (0..n).filter(|i| *i != k) {
+        let (early, late) = if i == 0 { (0, 0) } else { (tw[i].earliest, tw[i].latest) };
+        if early + travel(i, k) <= tw[k].latest {
+            earliest_arrival = Some(earliest_arrival.unwrap_or(usize::MAX).min(early + travel(i, k)));
+            latest_arrival   = latest_arrival.max(late + travel(i, k));
+        }
+    }
+    // When k has several time windows, its service starts at the opening of
+    // the first window that follows the arrival.
+    let earliest   = inst.next_opening_within(k, tw[k], earliest_arrival?)?;
+    let mut latest = inst.next_opening_within(k, tw[k], earliest.max(latest_arrival))
+        .unwrap_or(tw[k].latest);
+
+    // The latest time at which the service of k can start and still reach
+    // one of its feasible successors in time. (The return to the depot has
+    // no successor)
+    if k != 0 {
+        let latest_departure = (0..n)
+            .filter(|j| *j != k && tw[k].earliest + travel(k, *j) <= tw[*j].latest)
+            .map(|j| tw[j].latest - travel(k, j))
+            .max()?;
+        latest = latest.min(latest_departure);
+    }
+
+    if earliest <= latest {
+        Some(TimeWindow::new(earliest, latest))
+    } else {
+        None
+    }
+}
@@ -20,30 +20,107 @@
 //! This module contains the definition and implementation of the relaxation 
 //! for the TSP + TW problem.
 
-use std::ops::Not;
+use std::{collections::HashMap, ops::Not, str::FromStr};
 
 use bitset_fixed::BitSet;
-use ddo::{BitSetIter, Problem, Relaxation};
+use ddo::{BitSetIter, Matrix, Problem, Relaxation};
 
-use crate::{model::TSPTW, state::{ElapsedTime, Position, State}};
+use crate::{instance::End, model::{Objective, TSPTW}, state::{ElapsedTime, Load, Position, State, Vehicle}};
 use std::cell::RefCell;
 
+/// The maximum number of spanning tree weights that are remembered
+const MAX_CACHED_TREES: usize = 100_000;
+
+/// This enumeration lists the bounds that can be used to estimate the 
+/// distance that remains to be travelled from a given state
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Bound {
+    /// The sum of the cheapest edge entering each of the remaining nodes
+    #[default]
+    CheapestEdge,
+    /// The weight of a minimum spanning tree connecting the current position,
+    /// the nodes that must still be visited and the depot. (The bound which
+    /// is used is the best of this one and the cheapest edge bound)
+    SpanningTree,
+}
+impl FromStr for Bound {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "cheapest-edge"         => Ok(Bound::CheapestEdge),
+            "spanning-tree" | "mst" => Ok(Bound::SpanningTree),
+            _ => Err(format!("unknown bound '{}' (expected cheapest-edge or spanning-tree)", s))
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TSPTWRelax<'a> {
     pb : &'a TSPTW,
+    bound: Bound,
     cheapest_edge: Vec<usize>,
+    /// The shortest time it takes to reach a node when coming from any other
+    /// node (the service time at the origin is accounted for).
+    cheapest_arrival: Vec<usize>,
+    /// The shortest time it takes to leave a node (service included) and 
+    /// reach any other node. When the tour has a free end, this is merely
+    /// the service time (the salesman may stop right after any service).
+    cheapest_departure: Vec<usize>,
+    /// The earliest time at which any vehicle leaving the depot may reach a
+    /// node.
+    fresh_arrival: Vec<usize>,
+    /// The length of the shortest path between two nodes, whatever the 
+    /// direction of its edges. This is the weight of the edges of the 
+    /// spanning trees. (The path between two nodes of a tree may go through
+    /// any other node, optional customers included)
+    undirected: Matrix<usize>,
     helper: RefCell<RelaxHelper>,
 }
 impl <'a> TSPTWRelax<'a> {
     pub fn new(pb: &'a TSPTW) -> Self {
-        let cheapest_edge = Self::compute_cheapest_edges(pb);
-        let helper = RefCell::new(RelaxHelper::new(pb.nb_vars()));
-        Self{pb, cheapest_edge, helper}
+        let cheapest_edge    = Self::compute_cheapest_edges(pb);
+        let cheapest_arrival = Self::compute_cheapest_arrivals(pb);
+        let cheapest_departure = Self::compute_cheapest_departures(pb);
+        let fresh_arrival    = Self::compute_fresh_arrivals(pb);
+        let undirected       = Self::compute_undirected(pb);
+        let helper = RefCell::new(RelaxHelper::new(pb.instance.nb_nodes as usize));
+        Self{pb, bound: Bound::default(), cheapest_edge, cheapest_arrival, cheapest_departure, fresh_arrival, undirected, helper}
+    }
+    /// Changes the bound used to estimate the remaining distance
+    pub fn with_bound(mut self, bound: Bound) -> Self {
+        self.bound = bound;
+        self
+    }
+
+    fn compute_undirected(pb: &'a TSPTW) -> Matrix<usize> {
+        let n = pb.instance.nb_nodes as usize;
+        let d = &pb.instance.distances;
+        let mut undirected = Matrix::new_default(n, n, 0);
+        for i in 0..n {
+            for j in 0..n {
+                undirected[(i, j)] = d[(i, j)].min(d[(j, i)]);
+            }
+        }
+        // Floyd-Warshall: the distances need not satisfy the triangle 
+        // inequality
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    let via = undirected[(i, k)] + undirected[(k, j)];
+                    if via < undirected[(i, j)] {
+                        undirected[(i, j)] = via;
+                    }
+                }
+            }
+        }
+        undirected
     }
 
+    #[allow(clippy::legacy_numeric_constants)]
     fn compute_cheapest_edges(pb: &'a TSPTW) -> Vec<usize> {
         let mut cheapest = vec![];
-        let n = pb.nb_vars();
+        let n = pb.instance.nb_nodes as usize;
         for i in 0..n {
             let mut min_i = usize::max_value();
             for j in 0..n {
@@ -56,6 +133,47 @@ impl <'a> TSPTWRelax<'a> {
         }
         cheapest
     }
+
+    #[allow(clippy::legacy_numeric_constants)]
+    fn compute_cheapest_arrivals(pb: &'a TSPTW) -> Vec<usize> {
+        let mut cheapest = vec![];
+        let n = pb.instance.nb_nodes as usize;
+        for i in 0..n {
+            let mut min_i = usize::max_value();
+            for j in 0..n {
+                if i == j {
+                    continue;
+                }
+                min_i = min_i.min(pb.instance.service_times[j] + pb.instance.distances[(j, i)]);
+            }
+            cheapest.push(min_i);
+        }
+        cheapest
+    }
+    fn compute_fresh_arrivals(pb: &'a TSPTW) -> Vec<usize> {
+        let inst  = &pb.instance;
+        let start = (0..inst.fleet).map(|v| inst.shift(v).earliest).min().unwrap();
+        (0..inst.nb_nodes as usize).map(|i| start + pb.precedences.shortest(0, i)).collect()
+    }
+    #[allow(clippy::legacy_numeric_constants)]
+    fn compute_cheapest_departures(pb: &'a TSPTW) -> Vec<usize> {
+        let mut cheapest = vec![];
+        let n = pb.instance.nb_nodes as usize;
+        for i in 0..n {
+            let mut min_i = usize::max_value();
+            for j in 0..n {
+                if i == j {
+                    continue;
+                }
+                min_i = min_i.min(pb.instance.service_times[i] + pb.instance.distances[(i, j)]);
+            }
+            if pb.instance.end == End::Free {
+                min_i = pb.instance.service_times[i];
+            }
+            cheapest.push(min_i);
+        }
+        cheapest
+    }
 }
 #[derive(Clone)]
 struct RelaxHelper {
@@ -63,28 +181,62 @@ struct RelaxHelper {
     position : BitSet,
     earliest : usize,
     latest   : usize,
+    lightest : usize,
+    heaviest : usize,
+    first    : usize,
+    last     : usize,
     all_must : BitSet,
     all_agree: BitSet,
     all_maybe: BitSet,
     temp     : Vec<usize>,
+    /// The release date and processing time of the nodes that must still be
+    /// visited (when they are seen as jobs to schedule on a single machine)
+    jobs     : Vec<(usize, usize)>,
+    /// The weight of the spanning trees that were computed so far, indexed
+    /// by the set of nodes they connect. All the exact children of a given 
+    /// state connect the same set of nodes (the node they moved to merely
+    /// goes from the must visit set to the position). Hence, siblings share
+    /// the same tree.
+    trees    : HashMap<BitSet, usize>,
+    /// The set of nodes of the spanning tree being computed
+    tree_key : BitSet,
+    /// The nodes of the spanning tree being computed
+    tree_node: Vec<usize>,
+    /// The distance between each node and the spanning tree being computed
+    tree_dist: Vec<usize>,
 }
 impl RelaxHelper {
+    #[allow(clippy::legacy_numeric_constants)]
     fn new(n: usize) -> Self {
         Self {
             depth    : 0_u16,
             position : BitSet::new(n),
             earliest : usize::max_value(),
             latest   : usize::min_value(),
+            lightest : usize::max_value(),
+            heaviest : usize::min_value(),
+            first    : usize::max_value(),
+            last     : usize::min_value(),
             all_must : BitSet::new(n),
             all_agree: BitSet::new(n).not(),
             all_maybe: BitSet::new(n),
             temp     : vec![],
+            jobs     : vec![],
+            trees    : HashMap::new(),
+            tree_key : BitSet::new(n),
+            tree_node: vec![],
+            tree_dist: vec![],
         }
     }
+    #[allow(clippy::legacy_numeric_constants)]
     fn clear(&mut self) {
         self.depth    = 0_u16;
         self.earliest = usize::max_value();
         self.latest   = usize::min_value();
+        self.lightest = usize::max_value();
+        self.heaviest = usize::min_value();
+        self.first    = usize::max_value();
+        self.last     = usize::min_value();
         self.position .buffer_mut().iter_mut().for_each(|x| *x = 0);
         self.all_must .buffer_mut().iter_mut().for_each(|x| *x = 0);
         self.all_agree.buffer_mut().iter_mut().for_each(|x| *x = u64::max_value());
@@ -112,6 +264,14 @@ impl RelaxHelper {
             }
         };
     }
+    fn track_load(&mut self, load: Load) {
+        self.lightest = self.lightest.min(load.lightest());
+        self.heaviest = self.heaviest.max(load.heaviest());
+    }
+    fn track_vehicle(&mut self, vehicle: Vehicle) {
+        self.first = self.first.min(vehicle.first());
+        self.last  = self.last.max(vehicle.last());
+    }
     fn track_must_visit(&mut self, bs: &BitSet) {
         self.all_agree &= bs;
         self.all_must  |= bs;
@@ -135,6 +295,12 @@ impl RelaxHelper {
             ElapsedTime::FuzzyAmount {earliest: self.earliest, latest: self.latest}
         }
     }
+    fn get_load(&self) -> Load {
+        Load::between(self.lightest, self.heaviest)
+    }
+    fn get_vehicle(&self) -> Vehicle {
+        Vehicle::between(self.first, self.last)
+    }
     fn get_must_visit(&self) -> BitSet {
         self.all_agree.clone()
     }
@@ -153,6 +319,11 @@ impl RelaxHelper {
 }
 
 impl Relaxation<State> for TSPTWRelax<'_> {
+    /// Merges the given states. Only the nodes which all the states must
+    /// visit are kept in `must_visit`, all the others become optional. This
+    /// keeps the merged state sound w.r.t. the precedences: a node is only
+    /// withheld from the domain when one of its predecessors belongs to 
+    /// `must_visit`, that is when none of the merged states could visit it.
     fn merge_states(&self, states: &mut dyn Iterator<Item=&State>) -> State {
         let mut helper = self.helper.borrow_mut();
         helper.clear();
@@ -161,6 +332,8 @@ impl Relaxation<State> for TSPTWRelax<'_> {
             helper.track_depth(state.depth);
             helper.track_position(&state.position);
             helper.track_elapsed(state.elapsed);
+            helper.track_load(state.load);
+            helper.track_vehicle(state.vehicle);
             helper.track_must_visit(&state.must_visit);
             helper.track_maybe(&state.maybe_visit);
         }
@@ -169,6 +342,8 @@ impl Relaxation<State> for TSPTWRelax<'_> {
             depth      : helper.get_depth(),
             position   : helper.get_position(),
             elapsed    : helper.get_elapsed(),
+            load       : helper.get_load(),
+            vehicle    : helper.get_vehicle(),
             must_visit : helper.get_must_visit(),
             maybe_visit: helper.get_maybe_visit(),
         }
@@ -179,24 +354,53 @@ impl Relaxation<State> for TSPTWRelax<'_> {
     }
 
 
+    #[allow(clippy::legacy_numeric_constants)]
     fn estimate(&self, state  : &State) -> isize {
-       let mut complete_tour = self.pb.nb_vars() - state.depth as usize;
+       // Once the tour is over, nothing changes anymore
+       if self.pb.is_over(state) {
+           return 0;
+       }
+       // The number of customers that remain to be visited (the last 
+       // decision brings the salesman back to the depot or to the end of the
+       // tour). When other 
+       // vehicles may still leave the depot, some of these decisions might
+       // be returns to the depot.
+       let mut complete_tour = (self.pb.nb_vars() - 1).saturating_sub(state.depth as usize);
+       let spare_vehicles    = self.pb.instance.fleet - 1 - state.vehicle.first();
 
        let mut helper        = self.helper.borrow_mut(); 
        let mut mandatory     = 0;
-       let mut back_to_depot = usize::max_value();
+       let mut service       = 0;
+       let mut back_to_end   = usize::max_value();
+       let mut lateness      = 0;
+       let mut prizes        = 0;
+       let mut latency       = 0;
+       let mut min_weight    = usize::max_value();
        
        helper.temp.clear();
+       helper.jobs.clear();
 
        for i in BitSetIter::new(&state.must_visit) {
-           complete_tour -= 1;
+           // A relaxed state may have spent some of its decisions visiting
+           // nodes it was not required to visit. It might thus be left with
+           // more mandatory visits than it has decisions to make.
+           complete_tour = match complete_tour.checked_sub(1) {
+               Some(remaining) => remaining,
+               None            => return isize::min_value(),
+           };
            mandatory += self.cheapest_edge[i];
-           back_to_depot = back_to_depot.min(self.pb.instance.distances[(i, 0)]);
+           service   += self.pb.instance.service_times[i];
+           back_to_end = back_to_end.min(self.distance_to_end(i));
 
-           let latest   = self.pb.instance.timewindows[i].latest;
-           let earliest = state.elapsed.add_duration(self.cheapest_edge[i]).earliest();
-           if earliest > latest {
-               return isize::min_value();
+           let earliest = self.earliest_arrival(state, i, spare_vehicles);
+           match self.pb.instance.next_opening(i, earliest) {
+               None          => return isize::min_value(),
+               Some(release) => {
+                   lateness   += self.pb.instance.lateness_cost(i, release);
+                   latency    += self.pb.instance.weights[i] * release;
+                   min_weight  = min_weight.min(self.pb.instance.weights[i]);
+                   helper.jobs.push((release, self.cheapest_departure[i]))
+               },
            }
        }
 
@@ -205,54 +409,256 @@ impl Relaxation<State> for TSPTWRelax<'_> {
 
             for i in BitSetIter::new(maybes) {
                helper.temp.push(self.cheapest_edge[i]);
-               back_to_depot = back_to_depot.min(self.pb.instance.distances[(i, 0)]);
+               back_to_end = back_to_end.min(self.distance_to_end(i));
             
-               let latest   = self.pb.instance.timewindows[i].latest;
-               let earliest = state.elapsed.add_duration(self.cheapest_edge[i]).earliest();
-               if earliest > latest {
+               let earliest = self.earliest_arrival(state, i, spare_vehicles);
+               if self.pb.instance.next_opening(i, earliest).is_none() {
                    violations += 1;
+               } else {
+                   prizes += self.pb.instance.prize(i);
                }
             }
 
-            if helper.temp.len() - violations < complete_tour {
+            // When some customers are optional, the tour may end before all
+            // decisions are spent visiting nodes. At best, it collects the
+            // prizes of all the optional customers it can still reach.
+            let needed = if self.pb.instance.has_prizes() {
+                0
+            } else {
+                complete_tour.saturating_sub(spare_vehicles)
+            };
+            if helper.temp.len() - violations < needed {
                 return isize::min_value();
             }
 
             helper.temp.sort_unstable();
-            mandatory += helper.temp.iter().copied().take(complete_tour).sum::<usize>();
+            mandatory += helper.temp.iter().copied().take(needed).sum::<usize>();
        }
 
        // When there is no other city that MUST be visited, we must consider 
        // the shortest distance between *here* (current position) and the 
-       // depot.
+       // depot (or the end of the tour).
        if mandatory == 0 {
-           back_to_depot = back_to_depot.min(
+           back_to_end = back_to_end.min(
                match &state.position {
                 Position::Node(x) => 
-                    self.pb.instance.distances[(*x as usize, 0)],
+                    self.distance_to_end(*x as usize),
                 Position::Virtual(bs) =>
-                    BitSetIter::new(bs).map(|x| self.pb.instance.distances[(x, 0)]).min().unwrap()
+                    BitSetIter::new(bs).map(|x| self.distance_to_end(x)).min().unwrap()
            });
        }
 
-       // When it is impossible to get back to the depot in time, the current
-       // state is infeasible. So we can give it an infinitely negative ub.
-       let total_distance  = mandatory + back_to_depot;
-       let earliest_arrival= state.elapsed.add_duration(total_distance).earliest();
-       let latest_deadline = self.pb.instance.timewindows[0].latest;
-       if earliest_arrival > latest_deadline {
-           isize::min_value()
+       // The service at the current position must be completed before leaving
+       // (unless the vehicle might remain unused or the tour is over)
+       let end    = self.pb.end_node();
+       let unused = state.must_visit.count_ones() == 0 && (self.may_be_at(state, 0) || self.may_be_at(state, end));
+       let done   = state.depth as usize >= self.pb.nb_vars();
+       let here   = if unused || done { 0 } else { self.min_service_at(state) };
+       service   += here;
+
+       // The remaining path connects the current position, all the nodes that
+       // must still be visited and the depot. Between two of these nodes, it
+       // goes at least the length of the shortest path (which may go through
+       // the nodes that may be visited). It is thus no shorter than a 
+       // spanning tree of these nodes in the shortest path closure.
+       let mut total_distance = mandatory + back_to_end;
+       if self.bound == Bound::SpanningTree {
+           total_distance = total_distance.max(self.spanning_tree(&mut helper, state));
+       }
+
+       // The nodes that must still be visited can be seen as jobs to schedule
+       // on a single machine. Each of them is released at the earliest time 
+       // its service can start and it keeps the machine busy at least until 
+       // the salesman can reach another node. Processing the jobs by order of
+       // release yields the earliest time at which the last one can be done.
+       // It is a lower bound on the time when the salesman reaches the end of
+       // the tour. This only holds for the last vehicle: the others leave some
+       // of the nodes to the vehicles which are yet to leave the depot.
+       let completion = if spare_vehicles == 0 {
+           helper.jobs.sort_unstable();
+           helper.jobs.iter().fold(0, |time, (release, processing)| time.max(*release) + processing)
        } else {
-            -(total_distance as isize)
+           0
+       };
+
+       // When it is impossible to get back to the depot (or to reach the end
+       // node) in time, the current state is infeasible. So we can give it an
+       // infinitely negative ub. A free end can be reached at any time.
+       if spare_vehicles == 0 && !unused && !done && self.pb.instance.end != End::Free {
+           let earliest_arrival= state.elapsed.add_duration(total_distance + service).earliest().max(completion);
+           if self.pb.instance.next_opening(end, earliest_arrival).is_none() {
+               return isize::min_value();
+           }
+           // The penalties for the late services are lower bounded by 
+           // assuming each node is served as soon as possible
+           lateness += self.pb.instance.lateness_cost(end, earliest_arrival);
        }
+
+       // The time that separates the current state from the end of the tour
+       // is made of travel, waiting and service times. The relaxed 
+       // states must assume they are as late as possible.
+       let latest_start = state.elapsed.latest();
+       let elapsed_to   = |time: usize| time.saturating_sub(latest_start);
+       let cost = match self.pb.objective {
+           Objective::TravelTime => 
+               -((total_distance + lateness) as isize),
+           Objective::TravelAndWaiting => {
+               // This requires to know all the services that remain to be
+               // performed; hence the states which may visit other nodes 
+               // cannot use it.
+               let travel_and_waiting = if state.maybe_visit.is_none() {
+                   let services = service - here + self.max_service_at(state);
+                   elapsed_to(completion).saturating_sub(services)
+               } else {
+                   0
+               };
+               -((total_distance.max(travel_and_waiting) + lateness) as isize)
+           },
+           Objective::Makespan => 
+               -(((total_distance + service).max(elapsed_to(completion)) + lateness) as isize),
+           Objective::Cumulative => {
+               // The service of each node cannot start before its release.
+               // When the last vehicle is on the road, the k-th service can
+               // neither start before the k-th release nor before the 
+               // previous service plus the shortest processing time.
+               let chained = if spare_vehicles == 0 {
+                   let gap      = helper.jobs.iter().map(|(_, p)| *p).min().unwrap_or(0);
+                   let mut time = 0;
+                   let mut sum  = 0;
+                   for (release, _) in helper.jobs.iter() {
+                       time = time.max(*release);
+                       sum += time;
+                       time+= gap;
+                   }
+                   sum * min_weight
+               } else {
+                   0
+               };
+               -((latency.max(chained) + lateness) as isize)
+           },
+       };
+       cost + prizes as isize
+    }
+}
+
+impl TSPTWRelax<'_> {
+    /// The earliest time at which the salesman may reach node i. When other
+    /// vehicles may still leave the depot, node i might be reached by one
+    /// of these instead.
+    fn earliest_arrival(&self, state: &State, i: usize, spare_vehicles: usize) -> usize {
+        let earliest = state.elapsed.add_duration(self.cheapest_arrival[i]).earliest();
+        if spare_vehicles == 0 {
+            earliest
+        } else {
+            earliest.min(self.fresh_arrival[i])
+        }
+    }
+    /// Returns true iff the current position might be the given node
+    fn may_be_at(&self, state: &State, node: usize) -> bool {
+        match &state.position {
+            Position::Node(x)     => *x as usize == node,
+            Position::Virtual(bs) => bs[node],
+        }
     }
+    /// The shortest distance between node i and the end of the tour
+    fn distance_to_end(&self, i: usize) -> usize {
+        match self.pb.instance.end {
+            End::Depot      => self.pb.instance.distances[(i, 0)],
+            End::Node(node) => self.pb.instance.distances[(i, node)],
+            End::Free       => 0,
+        }
+    }
+    /// The shortest service time at the current position
+    fn min_service_at(&self, state: &State) -> usize {
+        match &state.position {
+            Position::Node(x)     => self.pb.instance.service_times[*x as usize],
+            Position::Virtual(bs) => 
+                BitSetIter::new(bs).map(|x| self.pb.instance.service_times[x]).min().unwrap()
+        }
+    }
+    /// The longest service time at the current position
+    fn max_service_at(&self, state: &State) -> usize {
+        match &state.position {
+            Position::Node(x)     => self.pb.instance.service_times[*x as usize],
+            Position::Virtual(bs) => 
+                BitSetIter::new(bs).map(|x| self.pb.instance.service_times[x]).max().unwrap()
+        }
+    }
+    /// Returns the weight of a minimum spanning tree connecting the current
+    /// position, the nodes that must still be visited and the depot (or the
+    /// end node, there is none when the tour has a free end). When the
+    /// position is not known exactly, the candidate positions are contracted
+    /// in one single node (whose distance to any other node is the shortest
+    /// distance between that node and any of the candidates).
+    fn spanning_tree(&self, h: &mut RelaxHelper, state: &State) -> usize {
+        let end = match self.pb.instance.end {
+            End::Free => None,
+            _         => Some(self.pb.end_node()),
+        };
+        h.tree_node.clear();
+        h.tree_node.extend(BitSetIter::new(&state.must_visit));
+        h.tree_node.extend(end.filter(|e| !state.must_visit[*e]));
 
+        match &state.position {
+            Position::Node(x) => {
+                let x = *x as usize;
+                if end != Some(x) {
+                    h.tree_node.push(x);
+                }
+                h.tree_key.clone_from(&state.must_visit);
+                if let Some(end) = end {
+                    h.tree_key.set(end, true);
+                }
+                h.tree_key.set(x, true);
+                if let Some(weight) = h.trees.get(&h.tree_key) {
+                    return *weight;
+                }
+                let weight = self.prim(&h.tree_node, &mut h.tree_dist, None);
+                if h.trees.len() >= MAX_CACHED_TREES {
+                    h.trees.clear();
+                }
+                h.trees.insert(h.tree_key.clone(), weight);
+                weight
+            },
+            Position::Virtual(candidates) => 
+                self.prim(&h.tree_node, &mut h.tree_dist, Some(candidates))
+        }
+    }
+    /// Computes the weight of a minimum spanning tree of the given nodes 
+    /// (plus the contracted candidates, if any) using Prim's algorithm.
+    #[allow(clippy::legacy_numeric_constants)]
+    fn prim(&self, nodes: &[usize], dist: &mut Vec<usize>, candidates: Option<&BitSet>) -> usize {
+        let w = &self.undirected;
+        let mut weight = 0;
+        let mut todo   = nodes.len();
+
+        // The tree is grown from the contracted candidates if there are any,
+        // and from the first node otherwise.
+        dist.clear();
+        match candidates {
+            Some(c) => dist.extend(nodes.iter()
+                .map(|&j| BitSetIter::new(c).map(|i| w[(i, j)]).min().unwrap())),
+            None    => {
+                dist.extend(nodes.iter().map(|&j| w[(nodes[0], j)]));
+                dist[0] = usize::max_value();
+                todo   -= 1;
+            }
+        }
 
-    // TODO An example RUB could be the weight of the spanning tree connecting 
-    //      the remaining nodes (... but that would be quite expensive to compute
-    //      at each node).
-    //
-    //      Another idea would be to simply consider the negated cost of the 
-    //      most expensive edge in the complete matrix and multiply that value
-    //      by the number of remaining nodes.
+        while todo > 0 {
+            let (next, d) = dist.iter().copied().enumerate()
+                .filter(|(_, d)| *d != usize::max_value())
+                .min_by_key(|(_, d)| *d)
+                .unwrap();
+            weight    += d;
+            todo      -= 1;
+            dist[next] = usize::max_value();
+            for (k, &j) in nodes.iter().enumerate() {
+                if dist[k] != usize::max_value() {
+                    dist[k] = dist[k].min(w[(nodes[next], j)]);
+                }
+            }
+        }
+        weight
+    }
 }
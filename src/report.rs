@@ -0,0 +1,281 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module defines the record which is produced for each solved instance
+//! and the various formats in which such a record can be written: a human
+//! readable table, csv or json (one record per line).
+
+use std::{io::{self, BufRead, Lines}, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{instance::TSPTWInstance, schedule::Stop};
+
+/// The formats in which a record can be written
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// A fixed width, pipe separated, line meant for humans
+    Table,
+    /// Comma separated values
+    Csv,
+    /// One json object per line
+    Json,
+}
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv"   => Ok(Format::Csv),
+            "json"  => Ok(Format::Json),
+            _ => Err(format!("unknown format '{}' (expected table, csv or json)", s))
+        }
+    }
+}
+impl Format {
+    /// Returns the header line of this format (if any)
+    pub fn header(self) -> Option<String> {
+        match self {
+            Format::Table => Some(format!("{:40} | {:10} | {:10} | {:10} | {:10} | {:8}",
+                                          "INSTANCE", "STATUS", "UB", "LB", "DURATION", "SOLUTION")),
+            Format::Csv   => Some("instance,status,ub,lb,gap,duration,threads,width,tour,arrivals,waits".to_string()),
+            Format::Json  => None,
+        }
+    }
+}
+
+/// One stop of the tour, as it is reported
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Visit {
+    pub node     : usize,
+    pub arrival  : f64,
+    pub wait     : f64,
+    pub start    : f64,
+    pub departure: f64,
+}
+impl Visit {
+    /// Converts a stop of the schedule to the unit of the instance file
+    pub fn new(inst: &TSPTWInstance, stop: &Stop) -> Self {
+        Self {
+            node     : stop.node,
+            arrival  : inst.to_decimal(stop.arrival as isize),
+            wait     : inst.to_decimal(stop.waiting() as isize),
+            start    : inst.to_decimal(stop.start as isize),
+            departure: inst.to_decimal(stop.departure as isize),
+        }
+    }
+}
+
+/// This is the outcome of the resolution of one instance. All quantities are
+/// expressed in the unit of the instance file. A missing bound means that
+/// bound is infinite.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// The name of the instance (benchmark/file)
+    pub instance   : String,
+    /// Either "Proved" or "Timeout"
+    pub status     : String,
+    /// The value of the best solution found so far
+    pub upper_bound: Option<f64>,
+    /// The best proved lower bound on the optimal value
+    pub lower_bound: Option<f64>,
+    /// The relative gap between the two bounds
+    pub gap        : Option<f64>,
+    /// The wall clock time spent solving the instance (in seconds)
+    pub duration   : f64,
+    /// The number of threads used by the solver
+    pub threads    : usize,
+    /// The width multiplier (if any was given)
+    pub width      : Option<usize>,
+    /// The best tour (from the depot back to the depot)
+    pub tour       : Option<Vec<usize>>,
+    /// The schedule of the best tour
+    pub schedule   : Option<Vec<Visit>>,
+    /// The number of decimals of the fixed point values of the instance. The
+    /// table and csv print the bounds with that many decimals.
+    #[serde(default)]
+    pub precision  : u32,
+}
+impl Record {
+    /// Computes the relative gap between the given bounds
+    pub fn gap(upper_bound: Option<f64>, lower_bound: Option<f64>) -> Option<f64> {
+        match (upper_bound, lower_bound) {
+            (Some(ub), Some(lb)) if ub.abs() > f64::EPSILON => Some((ub - lb) / ub.abs()),
+            (Some(_),  Some(_))  => Some(0.0),
+            _ => None
+        }
+    }
+    /// Writes this record in the requested format (without trailing newline)
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Table => format!("{:40} | {:10} | {:10} | {:10} | {:10.3} | {}",
+                                     self.instance,
+                                     self.status,
+                                     self.bound(self.upper_bound, "+inf"),
+                                     self.bound(self.lower_bound, "-inf"),
+                                     self.duration,
+                                     self.table_solution()),
+            Format::Csv   => format!("{},{},{},{},{},{:.3},{},{},{},{},{}",
+                                     csv_field(&self.instance),
+                                     csv_field(&self.status),
+                                     self.bound(self.upper_bound, "+inf"),
+                                     self.bound(self.lower_bound, "-inf"),
+                                     self.gap.map(|g| g.to_string()).unwrap_or_default(),
+                                     self.duration,
+                                     self.threads,
+                                     self.width.map(|w| w.to_string()).unwrap_or_default(),
+                                     join(self.tour.iter().flatten()),
+                                     join(self.schedule.iter().flatten().map(|v| v.arrival)),
+                                     join(self.schedule.iter().flatten().map(|v| v.wait))),
+            Format::Json  => serde_json::to_string(self).unwrap(),
+        }
+    }
+    /// Reads back a line that was written with `render` in the given format.
+    /// This returns None when the line is not a record (i.e. it is a header
+    /// or it is malformed). The fields which are not part of the format
+    /// (threads and width for the table, the schedule for the table and csv)
+    /// take their default value, the precision is that of the bounds.
+    pub fn parse(line: &str, format: Format) -> Option<Record> {
+        match format {
+            Format::Json  => serde_json::from_str(line).ok(),
+            Format::Csv   => {
+                let fields = csv_fields(line)?;
+                let fields = fields.iter().map(|f| f.trim()).collect::<Vec<_>>();
+                if fields.len() != 11 || fields[0] == "instance" {
+                    return None;
+                }
+                Some(Record {
+                    instance   : fields[0].to_string(),
+                    status     : fields[1].to_string(),
+                    upper_bound: parse_bound(fields[2])?,
+                    lower_bound: parse_bound(fields[3])?,
+                    gap        : parse_bound(fields[4])?,
+                    duration   : fields[5].parse().ok()?,
+                    threads    : fields[6].parse().ok()?,
+                    width      : if fields[7].is_empty() { None } else { Some(fields[7].parse().ok()?) },
+                    tour       : parse_tour(fields[8], false)?,
+                    schedule   : None,
+                    precision  : decimals(fields[2]).max(decimals(fields[3])),
+                })
+            },
+            Format::Table => {
+                let fields = line.split('|').map(str::trim).collect::<Vec<_>>();
+                if fields.len() != 6 || fields[0] == "INSTANCE" {
+                    return None;
+                }
+                let upper_bound = parse_bound(fields[2])?;
+                let lower_bound = parse_bound(fields[3])?;
+                Some(Record {
+                    instance   : fields[0].to_string(),
+                    status     : fields[1].to_string(),
+                    upper_bound,
+                    lower_bound,
+                    gap        : Record::gap(upper_bound, lower_bound),
+                    duration   : fields[4].parse().ok()?,
+                    threads    : 0,
+                    width      : None,
+                    tour       : parse_tour(fields[5], true)?,
+                    schedule   : None,
+                    precision  : decimals(fields[2]).max(decimals(fields[3])),
+                })
+            },
+        }
+    }
+    /// The tour as it was historically printed in the table (without the
+    /// initial depot)
+    fn table_solution(&self) -> String {
+        match &self.tour {
+            None       => "No feasible solution found".to_string(),
+            Some(tour) => tour.iter().skip(1).map(|x| format!(" {}", x)).collect()
+        }
+    }
+    /// Prints a (possibly infinite) bound with the precision of the instance
+    fn bound(&self, x: Option<f64>, inf: &str) -> String {
+        x.map(|x| format!("{:.*}", self.precision as usize, x)).unwrap_or_else(|| inf.to_string())
+    }
+}
+/// Quotes a csv field when it holds a comma, a quote or a line break (the
+/// quotes of the field are then doubled)
+fn csv_field(x: &str) -> String {
+    if x.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", x.replace('"', "\"\""))
+    } else {
+        x.to_string()
+    }
+}
+/// Splits a csv line in its (unquoted) fields. This returns None when some 
+/// quote is not closed.
+fn csv_fields(line: &str) -> Option<Vec<String>> {
+    let mut fields = vec![];
+    let mut field  = String::new();
+    let mut quoted = false;
+    let mut chars  = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => { chars.next(); field.push('"'); },
+            '"'                                         => quoted = !quoted,
+            ',' if !quoted                              => fields.push(std::mem::take(&mut field)),
+            _                                           => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+/// The number of decimals of a number as it was printed
+fn decimals(x: &str) -> u32 {
+    x.find('.').map_or(0, |dot| (x.len() - dot - 1) as u32)
+}
+/// Reads all the records from the given lines (which were written in the 
+/// given format). The lines which are not records are skipped.
+pub fn read_records<B: BufRead>(lines: Lines<B>, format: Format) -> io::Result<Vec<Record>> {
+    let mut records = vec![];
+    for line in lines {
+        if let Some(record) = Record::parse(&line?, format) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+/// Parses a (possibly infinite) bound. The outer option is None when the
+/// field is malformed.
+fn parse_bound(x: &str) -> Option<Option<f64>> {
+    match x {
+        "" | "+inf" | "-inf" => Some(None),
+        _ => x.parse().ok().map(Some)
+    }
+}
+/// Parses the tour of a csv or table line. The outer option is None when the
+/// field is malformed.
+fn parse_tour(x: &str, without_depot: bool) -> Option<Option<Vec<usize>>> {
+    if x.is_empty() || x == "No feasible solution found" {
+        return Some(None);
+    }
+    let mut tour = if without_depot { vec![0] } else { vec![] };
+    for node in x.split_whitespace() {
+        tour.push(node.parse().ok()?);
+    }
+    Some(Some(tour))
+}
+fn join<T: ToString>(xs: impl Iterator<Item=T>) -> String {
+    xs.map(|x| x.to_string()).collect::<Vec<_>>().join(" ")
+}
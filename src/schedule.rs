@@ -0,0 +1,94 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module computes the timing of a given route: when does the salesman
+//! arrive at each of the stops, when does he start serving it and when does
+//! he leave. The time it takes to travel along an arc may depend on the time
+//! at which the salesman leaves.
+
+use crate::instance::TSPTWInstance;
+
+/// This structure represents one stop along a route
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Stop {
+    /// The node being visited
+    pub node     : usize,
+    /// The time when the salesman reaches the node
+    pub arrival  : usize,
+    /// The time when the salesman starts serving the node (it is never
+    /// before the opening of the time window)
+    pub start    : usize,
+    /// The time when the salesman leaves the node
+    pub departure: usize,
+}
+impl Stop {
+    /// The time spent waiting for the time window to open
+    pub fn waiting(&self) -> usize {
+        self.start - self.arrival
+    }
+}
+
+/// Computes the schedule of the given route. The route is expected to be the
+/// complete sequence of nodes that are visited, (typically starting and
+/// ending with the depot). The salesman leaves the first node at time zero
+/// (plus the service time of that node) and never waits longer than needed:
+/// when he reaches a node between two of its time windows, he waits for the 
+/// next one to open.
+///
+/// When the route goes through the depot several times, each visit of the 
+/// depot ends the route of a vehicle and the next vehicle leaves the depot
+/// when its shift starts (at time zero when it has no shift). A vehicle 
+/// which goes straight back to the depot is not used: it stays at the depot
+/// during the whole stop.
+pub fn schedule(inst: &TSPTWInstance, route: &[usize]) -> Vec<Stop> {
+    let mut stops   = Vec::with_capacity(route.len());
+    let mut prev : Option<Stop> = None;
+    let mut vehicle = 0;
+    let mut leave   = 0;
+    for &node in route {
+        let stop = match prev {
+            None => {
+                let start = inst.shift(0).earliest;
+                Stop { node, arrival: start, start, departure: start + inst.service_times[node] }
+            },
+            Some(p) if p.node == 0 && node == 0 => {
+                let start = inst.shift(vehicle).earliest;
+                Stop { node, arrival: start, start, departure: start }
+            },
+            Some(p) => {
+                let arrival = leave + inst.travel_time(p.node, node, leave);
+                let start   = inst.next_opening(node, arrival)
+                    .unwrap_or_else(|| arrival.max(inst.timewindows[node].earliest));
+                Stop { node, arrival, start, departure: start + inst.service_times[node] }
+            }
+        };
+        // Once a vehicle is back at the depot, the next one leaves when its
+        // shift starts
+        if node == 0 && prev.is_some() {
+            vehicle += 1;
+            leave    = inst.shift(vehicle).earliest + inst.service_times[0];
+        } else {
+            leave    = stop.departure;
+        }
+
+        stops.push(stop);
+        prev = Some(stop);
+    }
+    stops
+}
@@ -0,0 +1,174 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module reads instances written in the coordinates-based format of the
+//! Solomon (and Gehring-Homberger) VRPTW benchmarks and turns them into a
+//! `TSPTWInstance`. In that format, each customer is described by a line
+//! holding its id, x and y coordinates, demand, ready time, due date and
+//! service time.
+//!
+//! The distance matrix is derived from the euclidean distance between any two
+//! customers. By default, and just like it was done for the SolomonPesant and
+//! SolomonPotvinBengio benchmarks, the service time of a customer is folded
+//! into the duration of all the edges leaving that customer (hence the time at
+//! which a customer is reached is the time when its service starts). The 
+//! reader can however be told to keep the service times apart.
+//!
+//! The customers must be listed in order of their ids, starting with the 
+//! depot (id 0).
+//!
+//! The demands of the customers and the capacity of the vehicles are ignored:
+//! the TSPTW benchmarks derived from these files do not constrain the load.
+
+use std::{io::{BufRead, Lines}, str::FromStr};
+
+use ddo::Matrix;
+
+use crate::instance::{fixed, number, tokens, ParseError, TSPTWInstance, TimeWindow, MAX_PRECISION};
+
+/// The number of values that are expected on each customer line
+const NB_FIELDS: usize = 7;
+
+/// This enumeration tells how the euclidean distances are brought down to
+/// the number of decimals that is kept.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rounding {
+    /// The distance is rounded to the nearest value (this is how the
+    /// SolomonPesant and SolomonPotvinBengio benchmarks were derived)
+    Nearest,
+    /// The extra decimals are simply dropped (Solomon's original convention
+    /// when used with one decimal)
+    Truncate,
+}
+impl FromStr for Rounding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "nearest"  | "round" => Ok(Rounding::Nearest),
+            "truncate" | "trunc" => Ok(Rounding::Truncate),
+            _ => Err(format!("unknown rounding '{}' (expected nearest or truncate)", s))
+        }
+    }
+}
+
+/// This structure knows how to read an instance in the Solomon format.
+#[derive(Debug, Copy, Clone)]
+pub struct SolomonReader {
+    /// How the euclidean distances are rounded
+    pub rounding : Rounding,
+    /// The number of decimals which are kept for the distances
+    pub precision: u32,
+    /// Whether the service times are folded into the distances (true) or
+    /// kept as the service times of the instance (false)
+    pub fold_service: bool,
+}
+impl Default for SolomonReader {
+    fn default() -> Self {
+        Self { rounding: Rounding::Nearest, precision: 4, fold_service: true }
+    }
+}
+impl SolomonReader {
+    /// This is how you create a new reader
+    pub fn new(rounding: Rounding, precision: u32) -> Self {
+        Self { rounding, precision: precision.min(MAX_PRECISION), fold_service: true }
+    }
+    /// Tells the reader whether or not it must fold the service times into
+    /// the distances
+    pub fn with_fold_service(mut self, fold_service: bool) -> Self {
+        self.fold_service = fold_service;
+        self
+    }
+    /// Reads the instance from the given lines.
+    pub fn read<B: BufRead>(&self, lines: Lines<B>) -> Result<TSPTWInstance, ParseError> {
+        let mut ln        = 0;
+        let mut in_section= false;
+        let mut customers = vec![];
+
+        for line in lines {
+            let line = line?;
+            ln += 1;
+
+            let mut toks = tokens(&line).peekable();
+            let first    = match toks.peek() {
+                None         => continue,
+                Some((_, t)) => *t,
+            };
+            // Everything before the customers section (name of the instance,
+            // vehicle fleet, ...) is irrelevant to us.
+            if !in_section {
+                in_section = first.eq_ignore_ascii_case("CUSTOMER");
+                continue;
+            }
+            // Skip the column headers, comments and trailing junk
+            if first.parse::<f64>().is_err() {
+                continue;
+            }
+
+            let mut values = vec![];
+            for (column, token) in toks {
+                values.push(number(ln, column, token)?);
+            }
+            if values.len() < NB_FIELDS {
+                return Err(ParseError::WrongRowLength{line: ln, expected: NB_FIELDS, found: values.len()});
+            }
+            // The customers are listed in order, starting with the depot
+            if values[0] != customers.len() as f64 {
+                return Err(ParseError::MisnumberedNode{line: ln, expected: customers.len(), found: first.to_string()});
+            }
+            customers.push(values);
+        }
+
+        if customers.is_empty() || customers.len() > u16::MAX as usize {
+            return Err(ParseError::BadNodeCount{line: ln + 1, column: 1, token: customers.len().to_string()});
+        }
+
+        let n         = customers.len();
+        let precision = self.precision;
+        let mut dist  = Matrix::new_default(n, n, 0);
+        let mut service_times = customers.iter()
+            .map(|c| fixed(c[6], precision))
+            .collect::<Vec<_>>();
+        for (i, ci) in customers.iter().enumerate() {
+            let service = if self.fold_service { service_times[i] } else { 0 };
+            for (j, cj) in customers.iter().enumerate() {
+                let dx = ci[1] - cj[1];
+                let dy = ci[2] - cj[2];
+                dist[(i, j)] = self.distance(dx.hypot(dy)) + service;
+            }
+        }
+        if self.fold_service {
+            service_times.iter_mut().for_each(|s| *s = 0);
+        }
+        let timewindows = customers.iter()
+            .map(|c| TimeWindow::new(fixed(c[4], precision), fixed(c[5], precision)))
+            .collect();
+
+        Ok(TSPTWInstance{service_times, precision, ..TSPTWInstance::new(dist, timewindows)})
+    }
+    /// Brings the euclidean distance to its fixed point representation
+    fn distance(&self, d: f64) -> usize {
+        match self.rounding {
+            Rounding::Nearest  => fixed(d, self.precision),
+            // the epsilon protects against values like 2.9999999 that are 
+            // really meant to be 3
+            Rounding::Truncate => (d * 10_f64.powi(self.precision as i32) + 1e-9) as usize,
+        }
+    }
+}
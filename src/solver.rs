@@ -0,0 +1,88 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module builds the solver used to find the best tour of a problem. 
+//! Both the command line tool and the tests go through it, so that they all
+//! solve the problems the same way. In particular, the variables are always
+//! decided in depth order (see `LoadVarsFromDepth`): this is what lets 
+//! `Tour::from_solution` rebuild the route from the decisions.
+
+use std::time::Duration;
+
+use ddo::{NoDupFrontier, ParallelSolver, Solver, TimeBudget, Times, config_builder};
+
+use crate::{heuristics::{IncreasingWithDepth, LoadVarsFromDepth}, model::TSPTW, relax::TSPTWRelax};
+
+/// Creates a solver for the given problem. The width of the layers is a 
+/// multiple of the `IncreasingWithDepth` heuristic (when `width` is given)
+/// and the search stops after `duration` seconds (if any).
+pub fn mk_solver<'a>(pb: &'a TSPTW, relax: TSPTWRelax<'a>, 
+                     verbosity: Option<u8>, 
+                     width:     Option<usize>,
+                     threads:   Option<usize>,
+                     duration:  Option<u64>) -> Box<dyn Solver + 'a> {
+    match (&width, &duration) {
+        (Some(w), Some(d)) => {
+            let mdd = config_builder(pb, relax)
+                .with_load_vars(LoadVarsFromDepth::new(pb))
+                .with_max_width(Times(*w, IncreasingWithDepth::new(pb)))
+                .with_cutoff(TimeBudget::new(Duration::from_secs(*d)))
+                .into_deep();
+            let solver = ParallelSolver::new(mdd)
+                .with_verbosity(verbosity.unwrap_or(0))
+                .with_nb_threads(threads.unwrap_or_else(num_cpus::get))
+                .with_frontier(NoDupFrontier::default());
+            Box::new(solver)
+        },
+        (Some(w), None) => {
+            let mdd = config_builder(pb, relax)
+                .with_load_vars(LoadVarsFromDepth::new(pb))
+                .with_max_width(Times(*w, IncreasingWithDepth::new(pb)))
+                .into_deep();
+            let solver = ParallelSolver::new(mdd)
+                .with_verbosity(verbosity.unwrap_or(0))
+                .with_nb_threads(threads.unwrap_or_else(num_cpus::get))
+                .with_frontier(NoDupFrontier::default());
+            Box::new(solver)
+        },
+        (None, Some(d)) => {
+            let mdd = config_builder(pb, relax)
+                .with_load_vars(LoadVarsFromDepth::new(pb))
+                .with_max_width(IncreasingWithDepth::new(pb))
+                .with_cutoff(TimeBudget::new(Duration::from_secs(*d)))
+                .into_deep();
+            let solver = ParallelSolver::new(mdd)
+                .with_verbosity(verbosity.unwrap_or(0))
+                .with_nb_threads(threads.unwrap_or_else(num_cpus::get))
+                .with_frontier(NoDupFrontier::default());
+            Box::new(solver)
+        },
+        (None, None) => {
+            let mdd = config_builder(pb, relax)
+                .with_load_vars(LoadVarsFromDepth::new(pb))
+                .with_max_width(IncreasingWithDepth::new(pb))
+                .into_deep();
+            let solver = ParallelSolver::new(mdd)
+                .with_verbosity(verbosity.unwrap_or(0))
+                .with_nb_threads(threads.unwrap_or_else(num_cpus::get))
+                .with_frontier(NoDupFrontier::default());
+            Box::new(solver)
+        }
+    }
+}
@@ -27,18 +27,29 @@ use bitset_fixed::BitSet;
 use crate::instance::TimeWindow;
 
 /// This represents a state of the problem: 
-/// the salesman is at a given position in his tour and a given amount of time
-/// has elapsed since he left the depot. Also, he keeps track of the nodes he
-/// has already been visiting and the ones which he may still need to visit.
+/// the salesman is at a given position in his tour, a given amount of time
+/// has elapsed since he left the depot and his vehicle carries a given load.
+/// When there are several vehicles, they leave the depot one after the other
+/// and the state tells which one is on the road.
+/// Also, he keeps track of the nodes he has already been visiting and the 
+/// ones which he may still need to visit.
 #[derive(Clone, Hash, PartialEq, Eq)]
 pub struct State {
     /// This is the current position of the salesman
     pub position : Position,
     /// The amount of time which has elapsed since the salesman left the depot
+    /// when he starts serving the node at his current position
     pub elapsed  : ElapsedTime,
+    /// The load of the vehicle when it leaves the current position
+    pub load     : Load,
+    /// The vehicle which is currently on the road (its index is the size of
+    /// the fleet once the tour is over)
+    pub vehicle  : Vehicle,
     /// These are the nodes he still has to visit
     pub must_visit : BitSet,
-    /// These are the nodes he still might visit but is not forced to
+    /// These are the nodes he still might visit but is not forced to: the 
+    /// optional customers and, in a relaxed state, the nodes which only some
+    /// of the merged states have yet to visit
     pub maybe_visit: Option<BitSet>,
     /// This is the 'depth' in the tour, the number of cities that have already
     /// been visited
@@ -99,3 +110,93 @@ impl ElapsedTime {
         }
     }
 }
+
+/// This represents the load of the vehicle. Just like the elapsed time, it
+/// is either a fixed amount (in the case of an exact node) or it lies 
+/// somewhere within an interval (in the case of an inexact node).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Load {
+    FixedLoad{
+        amount: usize
+    },
+    FuzzyLoad{
+        lightest: usize,
+        heaviest: usize
+    }
+}
+impl Load {
+    /// Creates the load lying in the given interval
+    pub fn between(lightest: usize, heaviest: usize) -> Self {
+        if lightest == heaviest {
+            Load::FixedLoad{amount: lightest}
+        } else {
+            Load::FuzzyLoad{lightest, heaviest}
+        }
+    }
+    pub fn lightest(self) -> usize {
+        match self {
+            Load::FixedLoad{amount}       => amount,
+            Load::FuzzyLoad{lightest, ..} => lightest
+        }
+    }
+    pub fn heaviest(self) -> usize {
+        match self {
+            Load::FixedLoad{amount}       => amount,
+            Load::FuzzyLoad{heaviest, ..} => heaviest
+        }
+    }
+    /// Returns true iff some load of this interval remains within the 
+    /// capacity of the vehicle (and non negative) after the given change
+    pub fn can_change_by(self, delta: isize, capacity: usize) -> bool {
+        self.lightest() as isize + delta <= capacity as isize
+            && self.heaviest() as isize + delta >= 0
+    }
+    /// Changes the load by the given amount. Only those loads which remain
+    /// within the capacity of the vehicle (and non negative) are kept.
+    pub fn change_by(self, delta: isize, capacity: usize) -> Self {
+        let lightest = (self.lightest() as isize + delta).max(0) as usize;
+        let heaviest = (self.heaviest() as isize + delta).min(capacity as isize).max(0) as usize;
+        Load::between(lightest.min(heaviest), heaviest)
+    }
+}
+
+/// This represents the vehicle which is on the road. Just like the elapsed 
+/// time, it is either known exactly (in the case of an exact node) or it is
+/// one among a range of vehicles (in the case of an inexact node).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Vehicle {
+    FixedVehicle{
+        index: usize
+    },
+    FuzzyVehicle{
+        first: usize,
+        last : usize
+    }
+}
+impl Vehicle {
+    /// Creates the vehicle lying in the given range
+    pub fn between(first: usize, last: usize) -> Self {
+        if first == last {
+            Vehicle::FixedVehicle{index: first}
+        } else {
+            Vehicle::FuzzyVehicle{first, last}
+        }
+    }
+    pub fn first(self) -> usize {
+        match self {
+            Vehicle::FixedVehicle{index}    => index,
+            Vehicle::FuzzyVehicle{first, ..} => first
+        }
+    }
+    pub fn last(self) -> usize {
+        match self {
+            Vehicle::FixedVehicle{index}   => index,
+            Vehicle::FuzzyVehicle{last, ..} => last
+        }
+    }
+    /// Returns the vehicle which leaves the depot after this one (among the
+    /// given number of vehicles)
+    pub fn next(self, fleet: usize) -> Self {
+        Vehicle::between((self.first() + 1).min(fleet - 1), (self.last() + 1).min(fleet - 1))
+    }
+}
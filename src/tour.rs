@@ -0,0 +1,299 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module defines the `Tour` type: a validated hamiltonian circuit which
+//! leaves the depot, visits every customer exactly once and then returns to
+//! the depot. A tour can either be rebuilt from the decisions of a solution
+//! found by the solver or from a permutation of the customers. When there
+//! are several vehicles, the tour goes through the depot between the routes
+//! of two consecutive vehicles. When the instance says so, the tour ends at
+//! some other node or right after the last customer (it is then a path 
+//! rather than a circuit). The customers which have a prize may be left 
+//! out of the tour.
+
+use std::{error, fmt};
+
+use ddo::{Decision, Problem, Solution, VarSet};
+
+use crate::{instance::{End, TSPTWInstance}, model::TSPTW};
+
+/// This enumeration lists the reasons why a sequence of nodes is not a tour
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TourError {
+    /// The solution holds a decision about a depth beyond the end of the tour
+    UnknownDepth(usize),
+    /// The solution holds no decision about the node visited at this depth
+    MissingDecision(usize),
+    /// The solution holds more than one decision about the node visited at
+    /// this depth
+    DuplicateDecision(usize),
+    /// The node visited at this depth cannot follow the nodes visited before
+    /// it (the variables were not decided in depth order)
+    ForbiddenDecision(usize),
+    /// The last move of the solution does not bring the salesman back to
+    /// the depot
+    NotBackToDepot,
+    /// The last move of the solution does not bring the salesman to the 
+    /// node where the tour ends
+    NotAtEnd(usize),
+    /// The permutation does not visit every customer exactly once
+    WrongLength { expected: usize, found: usize },
+    /// The depot appears in the middle of the permutation
+    DepotInPermutation,
+    /// The node where the tour ends appears in the middle of the permutation
+    EndInPermutation(usize),
+    /// This node does not exist in the instance
+    UnknownNode(usize),
+    /// This node is visited more than once
+    DuplicateNode(usize),
+    /// The tour is made of more routes than there are vehicles
+    TooManyRoutes { fleet: usize, found: usize },
+}
+impl fmt::Display for TourError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TourError::UnknownDepth(depth) =>
+                write!(f, "depth {} is beyond the end of the tour", depth),
+            TourError::MissingDecision(depth) =>
+                write!(f, "no node is visited at depth {}", depth),
+            TourError::DuplicateDecision(depth) =>
+                write!(f, "several nodes are visited at depth {}", depth),
+            TourError::ForbiddenDecision(depth) =>
+                write!(f, "the node visited at depth {} cannot follow the previous ones", depth),
+            TourError::NotBackToDepot =>
+                write!(f, "the tour does not end at the depot"),
+            TourError::NotAtEnd(node) =>
+                write!(f, "the tour does not end at node {}", node),
+            TourError::WrongLength{expected, found} =>
+                write!(f, "expected {} customers in the permutation but found {}", expected, found),
+            TourError::DepotInPermutation =>
+                write!(f, "provide the permutation without the depot (0)"),
+            TourError::EndInPermutation(node) =>
+                write!(f, "node {} ends the tour: it may only come last", node),
+            TourError::UnknownNode(node) =>
+                write!(f, "node {} does not exist", node),
+            TourError::DuplicateNode(node) =>
+                write!(f, "node {} appears two times in the solution", node),
+            TourError::TooManyRoutes{fleet, found} =>
+                write!(f, "the solution has {} routes but there are only {} vehicles", found, fleet),
+        }
+    }
+}
+impl error::Error for TourError {}
+
+/// A hamiltonian circuit starting and ending at the depot (node 0). It may
+/// go through the depot several times when there are several vehicles. It 
+/// may also end at some other node, or at the last customer when the tour 
+/// is open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tour {
+    route: Vec<usize>,
+    open : bool,
+}
+impl Tour {
+    /// Rebuilds the tour from the decisions of a solution. The position of
+    /// each node in the tour is given by the depth associated with the
+    /// variable of the decision (see `TSPTW::depth_of`) and not by the order
+    /// in which the decisions are stored in the solution. This only holds 
+    /// when the solver decided the variables in depth order (which the 
+    /// solvers of the `solver` module do): the decisions are thus replayed 
+    /// in that order and each of them must be allowed by the model.
+    pub fn from_solution(pb: &TSPTW, solution: &Solution) -> Result<Self, TourError> {
+        let n         = pb.nb_vars();
+        let mut nodes = vec![None; n];
+        for d in solution.iter() {
+            let depth = TSPTW::depth_of(d.variable);
+            if depth >= n {
+                return Err(TourError::UnknownDepth(depth));
+            }
+            if nodes[depth].is_some() {
+                return Err(TourError::DuplicateDecision(depth));
+            }
+            nodes[depth] = Some(d.value as usize);
+        }
+        let mut perm = Vec::with_capacity(n);
+        for (depth, node) in nodes.iter().enumerate() {
+            perm.push(node.ok_or(TourError::MissingDecision(depth))?);
+        }
+        let decisions = perm.clone();
+        // When the tour is open, its last move is a return to the depot which
+        // costs nothing (see `TSPTW::end_node`)
+        let end = pb.end_node();
+        if perm.pop() != Some(end) {
+            return Err(if end == 0 { TourError::NotBackToDepot } else { TourError::NotAtEnd(end) });
+        }
+        // When the tour is over early, the salesman stays at its end for the
+        // remaining decisions (only the returns to the depot which separate
+        // the routes of the vehicles are kept)
+        while perm.last() == Some(&end) && perm.iter().filter(|x| **x == end).count() >= pb.instance.fleet {
+            perm.pop();
+        }
+        let tour = Self::from_sequence(&pb.instance, &perm)?;
+
+        let vars      = VarSet::all(n);
+        let mut state = pb.initial_state();
+        for (depth, node) in decisions.into_iter().enumerate() {
+            let variable = TSPTW::variable_at(depth);
+            let value    = node as isize;
+            if !pb.domain_of(&state, variable).into_iter().any(|v| v == value) {
+                return Err(TourError::ForbiddenDecision(depth));
+            }
+            state = pb.transition(&state, &vars, Decision{variable, value});
+        }
+        Ok(tour)
+    }
+    /// Builds the tour of the given instance from the sequence of the nodes
+    /// it visits, as printed by the `solve` command (see `from_routes` when
+    /// there are several vehicles and `from_path` otherwise). Unlike these,
+    /// the sequence may leave out the customers which have a prize.
+    pub fn from_sequence(inst: &TSPTWInstance, sequence: &[usize]) -> Result<Self, TourError> {
+        let n   = inst.nb_nodes as usize;
+        let end = match inst.end {
+            End::Depot      => Some(0),
+            End::Node(node) => Some(node),
+            End::Free       => None,
+        };
+        let mut seq = sequence.to_vec();
+        if end.is_some() && seq.last() == end.as_ref() {
+            seq.pop();
+        }
+        // The customers which are left out are validated as if they were
+        // visited last
+        let skipped = inst.customers()
+            .filter(|i| inst.is_optional(*i) && !seq.contains(i))
+            .collect::<Vec<_>>();
+        seq.extend_from_slice(&skipped);
+
+        let mut tour = if inst.fleet > 1 {
+            Self::from_routes(n, inst.fleet, &seq)?
+        } else {
+            Self::from_path(n, inst.end, &seq)?
+        };
+        tour.route.retain(|node| !skipped.contains(node));
+        Ok(tour)
+    }
+    /// Builds the tour of several vehicles from the sequence of the nodes 
+    /// they visit, as printed by the `solve` command: the initial depot is
+    /// omitted, each depot in the sequence ends the route of one vehicle and
+    /// starts the route of the next one (the final depot may be omitted). 
+    /// Two consecutive depots mean that a vehicle remains unused.
+    pub fn from_routes(nb_nodes: usize, fleet: usize, sequence: &[usize]) -> Result<Self, TourError> {
+        let mut seq = sequence;
+        if seq.last() == Some(&0) {
+            seq = &seq[..seq.len() - 1];
+        }
+        let routes = seq.iter().filter(|x| **x == 0).count() + 1;
+        if routes > fleet {
+            return Err(TourError::TooManyRoutes{fleet, found: routes});
+        }
+        let customers = seq.iter().copied().filter(|x| *x != 0).collect::<Vec<_>>();
+        Self::from_permutation(nb_nodes, &customers)?;
+
+        let mut route = Vec::with_capacity(seq.len() + 2);
+        route.push(0);
+        route.extend_from_slice(seq);
+        route.push(0);
+        Ok(Self { route, open: false })
+    }
+    /// Builds the tour from a permutation of the customers. The permutation
+    /// may either omit the depot altogether or mention it as its very first
+    /// and/or very last node (which is how the `solve` command prints its
+    /// solutions).
+    pub fn from_permutation(nb_nodes: usize, permutation: &[usize]) -> Result<Self, TourError> {
+        Self::from_path(nb_nodes, End::Depot, permutation)
+    }
+    /// Builds the tour which visits the customers in the order of the given
+    /// permutation and then goes to the given end. The permutation may omit
+    /// the depot and the end node or mention them as its very first and 
+    /// very last node respectively.
+    pub fn from_path(nb_nodes: usize, end: End, permutation: &[usize]) -> Result<Self, TourError> {
+        let n = nb_nodes;
+        let (last, expected) = match end {
+            End::Depot      => (Some(0),    n - 1),
+            End::Node(node) => (Some(node), n - 2),
+            End::Free       => (None,       n - 1),
+        };
+
+        let mut perm = permutation;
+        if perm.len() > expected && last.is_some() && perm.last() == last.as_ref() {
+            perm = &perm[..perm.len() - 1];
+        }
+        if perm.len() > expected && perm.first() == Some(&0) {
+            perm = &perm[1..];
+        }
+        if perm.len() != expected {
+            return Err(TourError::WrongLength{expected, found: perm.len()});
+        }
+
+        let mut found = vec![false; n];
+        for &node in perm {
+            if node == 0 {
+                return Err(TourError::DepotInPermutation);
+            }
+            if node >= n {
+                return Err(TourError::UnknownNode(node));
+            }
+            if last == Some(node) {
+                return Err(TourError::EndInPermutation(node));
+            }
+            if found[node] {
+                return Err(TourError::DuplicateNode(node));
+            }
+            found[node] = true;
+        }
+
+        let mut route = Vec::with_capacity(n + 1);
+        route.push(0);
+        route.extend_from_slice(perm);
+        route.extend(last);
+        Ok(Self { route, open: last.is_none() })
+    }
+    /// The complete route, starting at the depot and ending at the end of
+    /// the tour
+    pub fn route(&self) -> &[usize] {
+        &self.route
+    }
+    /// Returns true iff the tour ends right after its last customer
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+    /// The customers in the order of their visit (without the initial depot
+    /// and the end of the tour, the depot still separates the routes of the
+    /// vehicles)
+    pub fn customers(&self) -> &[usize] {
+        let last = if self.open { self.route.len() } else { self.route.len() - 1 };
+        &self.route[1..last]
+    }
+    /// The route of each vehicle, from the depot back to the depot (a 
+    /// vehicle which is not used goes straight back to the depot)
+    pub fn routes(&self) -> Vec<&[usize]> {
+        let depots = (0..self.route.len()).filter(|k| self.route[*k] == 0).collect::<Vec<_>>();
+        depots.windows(2).map(|w| &self.route[w[0]..=w[1]]).collect()
+    }
+}
+impl fmt::Display for Tour {
+    /// Writes the visited nodes separated by spaces (the initial depot is
+    /// omitted but the end of the tour is not)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for node in self.route.iter().skip(1) {
+            write!(f, " {}", node)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use std::{convert::TryFrom, fs, io::{BufRead, Cursor}, path::PathBuf};
+
+use tsptw::{bench::{instance_name, instances, Output}, instance::TSPTWInstance, report::{Format, Record}};
+
+fn resources() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/resources")
+}
+fn record(instance: &str) -> Record {
+    Record {
+        instance   : instance.to_string(),
+        status     : "Proved".to_string(),
+        upper_bound: Some(671.0),
+        lower_bound: Some(671.0),
+        gap        : Some(0.0),
+        duration   : 0.25,
+        threads    : 1,
+        width      : Some(2),
+        tour       : Some(vec![0, 2, 1, 0]),
+        schedule   : None,
+        precision  : 0,
+    }
+}
+
+/// An instance which the tour of the records above fits
+fn instance() -> TSPTWInstance {
+    TSPTWInstance::try_from(Cursor::new("3\n0 1 1\n1 0 1\n1 1 0\n0 9\n0 9\n0 9\n").lines()).unwrap()
+}
+fn temp_file(name: &str, format: Format) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("tsptw_{}_{}_{:?}", name, std::process::id(), format));
+    let _    = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn a_directory_designates_all_the_files_it_holds() {
+    let dir   = resources().join("AFG");
+    let files = instances(dir.to_str().unwrap()).unwrap();
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), files.len());
+    assert!(files.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn a_glob_only_designates_the_matching_files() {
+    let pattern = resources().join("*/rbg01*.tw");
+    let files   = instances(pattern.to_str().unwrap()).unwrap();
+    let names   = files.iter().map(instance_name).collect::<Vec<_>>();
+    assert!(names.contains(&"AFG/rbg010a.tw".to_string()));
+    assert!(names.iter().all(|n| n.starts_with("AFG/rbg01")));
+}
+
+#[test]
+fn output_remembers_what_was_done() {
+    for format in [Format::Table, Format::Csv, Format::Json].iter().copied() {
+        let path = temp_file("bench", format);
+        let inst = instance();
+
+        let mut out = Output::open(&path, format).unwrap();
+        assert!(!out.is_done("AFG/rbg010a.tw", &inst));
+        out.write(&record("AFG/rbg010a.tw")).unwrap();
+        assert!(out.is_done("AFG/rbg010a.tw", &inst));
+        drop(out);
+
+        let mut out = Output::open(&path, format).unwrap();
+        assert!(out.is_done("AFG/rbg010a.tw", &inst));
+        assert!(!out.is_done("AFG/rbg016a.tw", &inst));
+        out.write(&record("AFG/rbg016a.tw")).unwrap();
+
+        // the header is written once, followed by one line per record
+        let lines = fs::read_to_string(&path).unwrap().lines().count();
+        let header= if format.header().is_some() { 1 } else { 0 };
+        assert_eq!(header + 2, lines);
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn a_half_written_record_is_dropped() {
+    for format in [Format::Table, Format::Csv, Format::Json].iter().copied() {
+        let path = temp_file("crash", format);
+        let inst = instance();
+
+        let mut out = Output::open(&path, format).unwrap();
+        out.write(&record("AFG/rbg010a.tw")).unwrap();
+        drop(out);
+        // The crash happens while the second record is being written
+        let line = record("AFG/rbg016a.tw").render(format);
+        let text = fs::read_to_string(&path).unwrap();
+        fs::write(&path, format!("{}{}", text, &line[..line.len() - 3])).unwrap();
+
+        let mut out = Output::open(&path, format).unwrap();
+        assert!(out.is_done("AFG/rbg010a.tw", &inst));
+        assert!(!out.is_done("AFG/rbg016a.tw", &inst));
+        out.write(&record("AFG/rbg016a.tw")).unwrap();
+        assert_eq!(format!("{}{}\n", text, line), fs::read_to_string(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn a_record_must_fit_the_instance() {
+    let path    = temp_file("fit", Format::Csv);
+    let mut out = Output::open(&path, Format::Csv).unwrap();
+    let mut rec = record("AFG/rbg010a.tw");
+    rec.tour    = Some(vec![0, 2]);
+    out.write(&rec).unwrap();
+    assert!(!out.is_done("AFG/rbg010a.tw", &instance()));
+
+    rec.tour    = None;
+    out.write(&rec).unwrap();
+    assert!(out.is_done("AFG/rbg010a.tw", &instance()));
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,27 @@
+// The tests of this file are generated by the build script: there is one
+// test per instance listed in the reference files (sol_<benchmark>.txt).
+// Hence, covering a new benchmark only requires to drop its reference file
+// at the root of the crate.
+mod test_utils;
+
+use tsptw::compare::tolerance;
+
+/// Solves the instance and checks that the optimal value is the expected one
+/// and that the best tour is feasible. The expected value is given as it is
+/// printed in the reference file: since it is rounded, the optimal value must
+/// only lie within half a unit of its last decimal. When the exact value is
+/// known (see the `#! exact` directive), the optimum must be that value.
+fn check(id: &str, expected: &str, exact: Option<&str>, width: Option<usize>) {
+    let (value, eval) = test_utils::solve_and_evaluate(id, width, Some(1));
+    let tolerance     = tolerance(expected);
+    let expected      = expected.parse::<f64>().unwrap();
+    assert!((value as f64 - expected).abs() <= tolerance, "expected {} but got {}", expected, value);
+    if let Some(exact) = exact {
+        assert_eq!(exact.parse::<f32>().unwrap(), value);
+    }
+
+    let eval = eval.expect("no solution found");
+    assert!(eval.is_feasible(), "the time windows of {:?} are violated", eval.violations);
+}
+
+include!(concat!(env!("OUT_DIR"), "/benchmarks.rs"));
@@ -0,0 +1,86 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use ddo::Relaxation;
+use tsptw::{instance::TSPTWInstance, model::{Objective, TSPTW}, relax::{Bound, TSPTWRelax}};
+
+mod test_utils;
+
+const INSTANCES: [&str; 4] = [
+    "Langevin/N20ft301.dat",
+    "Langevin/N40ft401.dat",
+    "AFG/rbg010a.tw",
+    "AFG/rbg016a.tw",
+];
+
+#[test]
+fn the_spanning_tree_bound_is_at_least_as_tight() {
+    for id in INSTANCES.iter() {
+        let pb       = TSPTW::new(test_utils::read(id));
+        let cheapest = TSPTWRelax::new(&pb);
+        let tree     = TSPTWRelax::new(&pb).with_bound(Bound::SpanningTree);
+        assert!(tree.estimate(&pb.initial) <= cheapest.estimate(&pb.initial), "{}", id);
+    }
+}
+
+#[test]
+fn both_bounds_find_the_same_optimum() {
+    for id in INSTANCES.iter() {
+        let pb    = TSPTW::new(test_utils::read(id));
+        let relax = TSPTWRelax::new(&pb).with_bound(Bound::SpanningTree);
+        let value = test_utils::solve_relaxed(&pb, relax, Some(10), Some(1));
+        assert_eq!(test_utils::solve_problem(&pb, Some(10), Some(1)), value, "{}", id);
+    }
+}
+
+// Both customers are close to the depot, but their windows only open at 100
+// and 110. Hence, the salesman cannot be back before 111.
+const LATE_WINDOWS: &str = "3
+0 1 1
+1 0 1
+1 1 0
+0   200
+100 200
+110 200
+";
+
+fn late_windows(objective: Objective) -> TSPTW {
+    let inst = TSPTWInstance::try_from(Cursor::new(LATE_WINDOWS).lines()).unwrap();
+    TSPTW::new(inst).with_objective(objective)
+}
+
+#[test]
+fn the_bound_accounts_for_forced_waiting() {
+    for objective in [Objective::TravelAndWaiting, Objective::Makespan].iter() {
+        let pb    = late_windows(*objective);
+        let relax = TSPTWRelax::new(&pb);
+        assert_eq!(-111, relax.estimate(&pb.initial), "{:?}", objective);
+        assert_eq!(111.0, test_utils::solve_problem(&pb, None, Some(1)), "{:?}", objective);
+    }
+    // Waiting is free when only the travel time matters
+    let pb    = late_windows(Objective::TravelTime);
+    let relax = TSPTWRelax::new(&pb);
+    assert_eq!(-3, relax.estimate(&pb.initial));
+}
+
+#[test]
+fn the_bound_detects_a_missed_depot_deadline() {
+    let inst = LATE_WINDOWS.replace("0   200", "0   110");
+    let pb   = TSPTW::new(TSPTWInstance::try_from(Cursor::new(inst).lines()).unwrap());
+    let relax= TSPTWRelax::new(&pb);
+    assert_eq!(isize::MIN, relax.estimate(&pb.initial));
+}
+
+#[test]
+fn the_spanning_tree_goes_through_the_optional_customers() {
+    // The shortest way from the depot to customer 2 (and to the end of the
+    // tour) goes through the optional customer 1
+    let text  = "4\n0 10 100 100\n10 0 10 100\n100 10 0 10\n100 100 10 0\n0 500\n0 500\n0 500\n0 500\nEND 3\nPRIZES\n1 5\n";
+    let inst  = TSPTWInstance::try_from(Cursor::new(text).lines()).unwrap();
+    let pb    = TSPTW::new(inst);
+    let tree  = TSPTWRelax::new(&pb).with_bound(Bound::SpanningTree);
+    // The tree weighs 30 and the prize of customer 1 might be collected
+    assert_eq!(-25, tree.estimate(&pb.initial));
+
+    let relax = TSPTWRelax::new(&pb).with_bound(Bound::SpanningTree);
+    assert_eq!(30.0, test_utils::solve_relaxed(&pb, relax, Some(10), Some(1)));
+}
@@ -0,0 +1,78 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{check::evaluate, instance::TSPTWInstance, model::TSPTW, state::Load, tour::Tour};
+
+mod test_utils;
+
+// Two pickups (1 and 2) and their deliveries (3 and 4) along a line. The
+// shortest tour picks both parcels before delivering them, which a vehicle
+// carrying one parcel at a time cannot do.
+const INSTANCE: &str = "5
+0 1 2 3 4
+1 0 1 2 3
+2 1 0 1 2
+3 2 1 0 1
+4 3 2 1 0
+0 100
+0 100 0 1
+0 100 0 1
+0 100 0 -1
+0 100 0 -1
+CAPACITY 1
+PRECEDENCES
+1 3
+2 4
+";
+
+fn instance(text: &str) -> TSPTWInstance {
+    TSPTWInstance::try_from(Cursor::new(text).lines()).unwrap()
+}
+
+#[test]
+fn loads_and_capacity_are_parsed() {
+    let inst = instance(INSTANCE);
+    assert_eq!(vec![0, 1, 1, -1, -1], inst.loads);
+    assert_eq!(Some(1), inst.capacity);
+    assert_eq!(vec![(1, 3), (2, 4)], inst.precedences);
+
+    let inst = instance(&INSTANCE.replace("CAPACITY 1\n", ""));
+    assert_eq!(None, inst.capacity);
+}
+
+#[test]
+fn the_capacity_is_never_exceeded() {
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&TSPTW::new(instance(INSTANCE)), Some(2), Some(1));
+    let eval          = eval.unwrap();
+    assert_eq!(10.0, value);
+    assert_eq!(vec![1, 3, 2, 4], eval.tour.customers());
+    assert_eq!(vec![0, 1, 0, 1, 0, 0], eval.loads);
+    assert!(eval.is_feasible());
+}
+
+#[test]
+fn the_load_is_free_without_capacity() {
+    let inst  = instance(&INSTANCE.replace("CAPACITY 1\n", ""));
+    let value = test_utils::solve_problem(&TSPTW::new(inst), Some(2), Some(1));
+    assert_eq!(8.0, value);
+}
+
+#[test]
+fn overloads_are_reported_by_the_checker() {
+    let inst = instance(INSTANCE);
+    let tour = Tour::from_permutation(5, &[1, 2, 3, 4]).unwrap();
+    let eval = evaluate(&inst, &tour);
+    assert_eq!(vec![2], eval.overloads);
+    assert!(!eval.is_feasible());
+}
+
+#[test]
+fn fuzzy_loads_keep_what_fits_in_the_vehicle() {
+    let load = Load::between(1, 3);
+    assert!(load.can_change_by(2, 3));
+    assert!(!load.can_change_by(3, 3));
+    assert!(load.can_change_by(-3, 3));
+    assert!(!load.can_change_by(-4, 3));
+    assert_eq!(Load::FuzzyLoad{lightest: 3, heaviest: 4}, load.change_by(2, 4));
+    assert_eq!(Load::FixedLoad{amount: 3}, load.change_by(2, 3));
+    assert_eq!(Load::FuzzyLoad{lightest: 0, heaviest: 1}, load.change_by(-2, 3));
+}
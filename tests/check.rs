@@ -0,0 +1,101 @@
+use std::{convert::TryFrom, fs::File, io::{BufRead, Cursor}, path::PathBuf, process::Command};
+
+use tsptw::{check::{check, evaluate, read_permutation, CheckError}, instance::TSPTWInstance, tour::{Tour, TourError}};
+
+fn instance(id: &str) -> TSPTWInstance {
+    let path = PathBuf::new()
+        .join(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/resources/")
+        .join(id);
+    TSPTWInstance::try_from(File::open(path).unwrap()).unwrap()
+}
+fn permutation(text: &str) -> Result<Vec<usize>, CheckError> {
+    read_permutation(Cursor::new(text).lines())
+}
+
+#[test]
+fn best_known_solution_is_feasible() {
+    let inst = instance("Langevin/N20ft301.dat");
+    let text = "15 18 4 3 2 16 14 11 8 17 7 1 6 5 13 9 10 12 19";
+    let eval = check(&inst, Cursor::new(text).lines()).unwrap();
+
+    assert!(eval.is_feasible());
+    assert_eq!(6616, eval.makespan);
+    assert_eq!(21, eval.tour.route().len());
+    assert_eq!(21, eval.stops.len());
+    assert!(eval.tour_cost <= eval.makespan);
+}
+
+#[test]
+fn distances_are_rounded_to_the_precision() {
+    // This makespan used to be 860.1748 when the distances were parsed as f32
+    // and truncated to four decimals
+    let mut inst = instance("SolomonPotvinBengio/rc_201.2.txt");
+    let text     = "13 21 10 23 9 12 5 6 8 16 19 25 17 18 1 2 24 11 7 3 4 14 20 22 15";
+    let eval     = check(&inst, Cursor::new(text).lines()).unwrap();
+    assert!(eval.is_feasible());
+    assert_eq!(5, inst.precision);
+    assert_eq!(86017490, eval.makespan);
+
+    inst.set_precision(4);
+    let eval     = check(&inst, Cursor::new(text).lines()).unwrap();
+    assert_eq!(8601749, eval.makespan);
+}
+
+#[test]
+fn violated_windows_are_reported() {
+    let inst = instance("AFG/rbg010a.tw");
+    let tour = Tour::from_permutation(11, &[1, 2, 3, 4, 5, 6, 7, 8, 10, 9]).unwrap();
+    let eval = evaluate(&inst, &tour);
+
+    assert!(!eval.is_feasible());
+    assert_eq!(vec![9], eval.violations);
+    assert_eq!(3903, eval.makespan);
+    assert_eq!(537, eval.delays[10]);
+}
+
+#[test]
+fn invalid_tours_are_rejected() {
+    let inst = instance("AFG/rbg010a.tw");
+    match check(&inst, Cursor::new("1 2 2").lines()) {
+        Err(CheckError::InvalidTour(TourError::WrongLength{expected: 10, found: 3})) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn output_of_solve_can_be_read() {
+    let text = "\
+INSTANCE                                 | STATUS     | UB         | LB         | DURATION   | SOLUTION
+AFG/rbg010a.tw                           | Proved     | 3840       | 3840       |      0.140 |  1 2 3 4 5 6 7 8 9 10 0
+    NODE |      ARRIVAL |         WAIT |        START |    DEPARTURE
+       0 |            0 |            0 |            0 |            0
+";
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 0], permutation(text).unwrap());
+
+    let text = "AFG/rbg010a.tw | Timeout | +inf | 12 | 1.0 | No feasible solution found\n";
+    match permutation(text) {
+        Err(CheckError::NoSolution) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn every_output_format_of_solve_can_be_checked() {
+    let inst = instance("AFG/rbg010a.tw");
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/resources/AFG/rbg010a.tw");
+    let mut tours = vec![];
+    for format in ["table", "csv", "json"].iter() {
+        let output = Command::new(env!("CARGO_BIN_EXE_tsptw"))
+            .arg("solve").arg(&path).args(["-t", "1", "--header", "--format", format])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", format);
+        let text = String::from_utf8(output.stdout).unwrap();
+        let eval = check(&inst, Cursor::new(text).lines()).unwrap();
+        assert!(eval.is_feasible(), "{}", format);
+        tours.push(eval.tour.route().to_vec());
+    }
+    assert!(tours.iter().all(|t| *t == tours[0]));
+}
+
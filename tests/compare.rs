@@ -0,0 +1,143 @@
+use std::{fs::File, io::{BufRead, BufReader, Cursor}, path::PathBuf};
+
+use tsptw::{compare::{compare, ReferenceError, References, Verdict}, report::Record};
+
+fn locate(fname: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(fname)
+}
+fn references() -> References {
+    let mut refs = References::default();
+    refs.load(Cursor::new("# Instance Cost CV Permutation\n\
+                           a.txt  100.00  0  1 2 3\n\
+                           b.txt  200.50  0  3 2 1  # Found by someone\n\
+                           c.txt  300.00  0  2 1 3\n").lines()).unwrap();
+    refs
+}
+fn record(instance: &str, status: &str, ub: Option<f64>, lb: Option<f64>) -> Record {
+    Record {
+        instance   : instance.to_string(),
+        status     : status.to_string(),
+        upper_bound: ub,
+        lower_bound: lb,
+        gap        : Record::gap(ub, lb),
+        duration   : 1.0,
+        threads    : 1,
+        width      : None,
+        tour       : None,
+        schedule   : None,
+        precision  : 0,
+    }
+}
+
+#[test]
+fn the_shipped_reference_files_can_be_loaded() {
+    let mut refs = References::default();
+    for fname in ["sol_langevin.txt", "sol_solomon_potvin_bengio.txt"].iter() {
+        let file = File::open(locate(fname)).unwrap();
+        refs.load(BufReader::new(file).lines()).unwrap();
+    }
+    assert_eq!(70 + 30, refs.len());
+
+    let best = refs.get("Langevin/N20ft309.dat").unwrap();
+    assert_eq!(751.8, best.value);
+    assert_eq!(19, best.permutation.len());
+    assert_eq!(Some("Found by Andy Doucette (2018)"), best.comment.as_deref());
+}
+
+#[test]
+fn the_tolerance_follows_the_decimals_of_the_reference() {
+    let refs = references();
+    assert_eq!(0.005, refs.get("a.txt").unwrap().tolerance);
+
+    let mut refs = References::default();
+    refs.load(Cursor::new("a.txt 100 0\nb.txt 860.17 0\nc.txt 75.1 0\n").lines()).unwrap();
+    assert_eq!(0.5,   refs.get("a.txt").unwrap().tolerance);
+    assert_eq!(0.005, refs.get("b.txt").unwrap().tolerance);
+    assert_eq!(0.05,  refs.get("c.txt").unwrap().tolerance);
+
+    // The published values are rounded: the exact makespan of the tour matches
+    let records = vec![
+        record("X/a.txt", "Proved", Some(100.4),     Some(100.4)),
+        record("X/b.txt", "Proved", Some(860.1749),  Some(860.1749)),
+        record("X/c.txt", "Proved", Some(75.06),     Some(75.06)),
+    ];
+    assert!(compare(&refs, &[], &records).iter().all(|c| c.verdict == Verdict::Match));
+
+    let records = vec![
+        record("X/a.txt", "Proved", Some(99.4),      Some(99.4)),
+        record("X/b.txt", "Proved", Some(860.1751),  Some(860.1751)),
+    ];
+    let verdicts = compare(&refs, &[], &records).iter()
+        .map(|c| c.verdict)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Verdict::Improvement, Verdict::WrongClaim], verdicts);
+}
+
+#[test]
+fn malformed_reference_lines_are_reported() {
+    let mut refs = References::default();
+    match refs.load(Cursor::new("a.txt 10 0 1 2\nb.txt ten 0 1 2\n").lines()) {
+        Err(ReferenceError::NotANumber{line: 2, token}) => assert_eq!("ten", token),
+        other => panic!("unexpected {:?}", other),
+    }
+    match refs.load(Cursor::new("a.txt\n").lines()) {
+        Err(ReferenceError::MissingValue{line: 1}) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn records_are_compared_with_the_best_known_solutions() {
+    let records = vec![
+        record("X/a.txt", "Proved",  Some(100.0), Some(100.0)),
+        record("X/b.txt", "Timeout", Some(199.0), Some(150.0)),
+        record("X/c.txt", "Timeout", Some(310.0), Some(250.0)),
+        record("X/d.txt", "Proved",  Some(10.0),  Some(10.0)),
+    ];
+    let verdicts = compare(&references(), &[], &records).iter()
+        .map(|c| c.verdict)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Verdict::Match, Verdict::Improvement, Verdict::WorseValue, Verdict::Unknown], verdicts);
+}
+
+#[test]
+fn a_proved_optimum_above_a_known_solution_is_a_wrong_claim() {
+    let records = vec![record("X/a.txt", "Proved", Some(105.0), Some(105.0))];
+    assert_eq!(Verdict::WrongClaim, compare(&references(), &[], &records)[0].verdict);
+}
+
+#[test]
+fn losing_a_proof_of_the_baseline_is_a_regression() {
+    let baseline = vec![record("X/a.txt", "Proved",  Some(100.0), Some(100.0))];
+    let records  = vec![record("X/a.txt", "Timeout", Some(100.0), Some(90.0))];
+    let outcome  = compare(&References::default(), &baseline, &records);
+    assert_eq!(Verdict::LostProof, outcome[0].verdict);
+    assert!(outcome[0].verdict.is_regression());
+    assert_eq!(Some(100.0), outcome[0].best_known);
+}
+
+#[test]
+fn the_tolerance_comes_from_the_source_of_the_best_known_value() {
+    // The baseline supplies the best value: it is not rounded
+    let baseline = vec![record("X/a.txt", "Timeout", Some(99.999),  Some(90.0))];
+    let records  = vec![record("X/a.txt", "Timeout", Some(100.003), Some(90.0))];
+    let outcome  = compare(&references(), &baseline, &records);
+    assert_eq!(Some(99.999), outcome[0].best_known);
+    assert_eq!(Verdict::WorseValue, outcome[0].verdict);
+
+    // The reference supplies the best value: it is rounded
+    let baseline = vec![record("X/a.txt", "Timeout", Some(100.2),   Some(90.0))];
+    let outcome  = compare(&references(), &baseline, &records);
+    assert_eq!(Some(100.0), outcome[0].best_known);
+    assert_eq!(Verdict::Match, outcome[0].verdict);
+}
+
+#[test]
+fn references_violating_some_constraint_are_ignored() {
+    let mut refs = References::default();
+    refs.load(Cursor::new("a.txt 90.00 2 1 2 3\n").lines()).unwrap();
+    let records  = vec![record("X/a.txt", "Proved", Some(100.0), Some(100.0))];
+    let outcome  = compare(&refs, &[], &records);
+    assert_eq!(None, outcome[0].best_known);
+    assert_eq!(Verdict::Unknown, outcome[0].verdict);
+}
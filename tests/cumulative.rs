@@ -0,0 +1,108 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{check::{check, evaluate}, instance::{Lateness, ParseError, TSPTWInstance, TimeWindow}, model::{Objective, TSPTW}, relax::Bound, tour::Tour};
+
+mod test_utils;
+use test_utils::{assert_optimal_on, random_distances, Lcg};
+
+// The nodes lie on a line: customer 1 is right next to the depot and
+// customer 2 lies on the other side. Both tours travel the same distance but
+// customer 2 waits much longer when it is served first.
+const INSTANCE: &str = "3
+0  1  10
+1  0  11
+10 11 0
+0 100
+0 100
+0 100
+";
+
+fn parse(text: &str) -> Result<TSPTWInstance, ParseError> {
+    TSPTWInstance::try_from(Cursor::new(text).lines())
+}
+fn solve(inst: TSPTWInstance) -> (f32, Vec<usize>) {
+    let pb = TSPTW::new(inst).with_objective(Objective::Cumulative);
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+    (value, eval.unwrap().tour.route().to_vec())
+}
+
+#[test]
+fn weights_are_parsed() {
+    let inst = parse(INSTANCE).unwrap();
+    assert_eq!(vec![1, 1, 1], inst.weights);
+    assert!(!inst.has_weights());
+
+    let inst = parse(&format!("{}WEIGHTS\n2 20\n", INSTANCE)).unwrap();
+    assert_eq!(vec![1, 1, 20], inst.weights);
+    assert!(inst.has_weights());
+
+    assert_eq!(Ok(Objective::Cumulative), "cumulative".parse());
+}
+
+#[test]
+fn invalid_weights_are_rejected() {
+    match parse(&format!("{}WEIGHTS\n5 2\n", INSTANCE)) {
+        Err(ParseError::UnknownNode{line: 9, node: 5}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&format!("{}WEIGHTS\n2 1.5\n", INSTANCE)) {
+        Err(ParseError::NotANumber{line: 9, column: 3, ..}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&format!("{}WEIGHTS\n2\n", INSTANCE)) {
+        Err(ParseError::WrongRowLength{line: 9, expected: 2, found: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn the_latency_of_a_tour_is_evaluated() {
+    let inst = parse(INSTANCE).unwrap();
+    let eval = evaluate(&inst, &Tour::from_permutation(3, &[1, 2]).unwrap());
+    assert_eq!(1 + 12, eval.latency);
+
+    let inst = parse(&format!("{}WEIGHTS\n2 20\n", INSTANCE)).unwrap();
+    let eval = check(&inst, Cursor::new(" 2 1 0").lines()).unwrap();
+    assert_eq!(20 * 10 + 21, eval.latency);
+}
+
+#[test]
+fn the_customers_are_served_as_early_as_possible() {
+    assert_eq!((13.0, vec![0, 1, 2, 0]), solve(parse(INSTANCE).unwrap()));
+
+    // Customer 2 weighs so much that it must be served first
+    assert_eq!((221.0, vec![0, 2, 1, 0]), solve(parse(&format!("{}WEIGHTS\n2 20\n", INSTANCE)).unwrap()));
+
+    // Customer 1 opens late: it is better served after customer 2
+    assert_eq!((40.0, vec![0, 2, 1, 0]), solve(parse(&INSTANCE.replacen("0 100\n0 100", "0 100\n30 100", 1)).unwrap()));
+}
+
+fn random_instance(rng: &mut Lcg, n: usize) -> TSPTWInstance {
+    let distances   = random_distances(rng, n);
+    let timewindows = (0..n)
+        .map(|i| if i == 0 {
+            TimeWindow::new(0, 100 + rng.next(100))
+        } else {
+            let earliest = rng.next(60);
+            TimeWindow::new(earliest, earliest + 10 + rng.next(40))
+        })
+        .collect();
+    let lateness = (0..n)
+        .map(|_| match rng.next(6) {
+            0 => Some(Lateness { penalty: 1 + rng.next(3), max: None }),
+            _ => None,
+        })
+        .collect();
+    TSPTWInstance {
+        lateness,
+        service_times: (0..n).map(|i| if i == 0 { 0 } else { rng.next(5) }).collect(),
+        weights      : (0..n).map(|_| 1 + rng.next(4)).collect(),
+        ..TSPTWInstance::new(distances, timewindows)
+    }
+}
+
+#[test]
+fn the_optimum_is_found_on_random_instances() {
+    let mut rng = Lcg(17);
+    assert_optimal_on(|| random_instance(&mut rng, 7), 100, &[Objective::Cumulative], &[Bound::CheapestEdge, Bound::SpanningTree]);
+}
@@ -0,0 +1,78 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{explain::{explain, obvious_conflicts, Conflict}, instance::TSPTWInstance};
+
+mod test_utils;
+
+// Customers 1, 2 and 3 share the same location but there is only time to
+// serve two of them. Customers 4 and 5 can be served at any time.
+const INSTANCE: &str = "6
+0 5  5  5  5  5
+5 0  0  0  10 10
+5 0  0  0  10 10
+5 0  0  0  10 10
+5 10 10 10 0  10
+5 10 10 10 10 0
+0 1000
+5 12 4
+5 12 4
+5 12 4
+0 1000
+0 1000
+";
+
+fn instance(text: &str) -> TSPTWInstance {
+    TSPTWInstance::try_from(Cursor::new(text).lines()).unwrap()
+}
+fn feasible(inst: &TSPTWInstance) -> Option<bool> {
+    Some(test_utils::solve_instance(inst.clone(), None, Some(1)) >= 0.0)
+}
+
+#[test]
+fn an_instance_can_be_restricted_to_some_customers() {
+    let mut inst = instance(INSTANCE);
+    inst.add_precedence(4, 2).unwrap();
+    inst.add_precedence(1, 5).unwrap();
+    let sub = inst.restrict(&[4, 2]);
+    assert_eq!(3, sub.nb_nodes);
+    assert_eq!(10, sub.distances[(1, 2)]);
+    assert_eq!(5,  sub.distances[(0, 2)]);
+    assert_eq!(12, sub.timewindows[2].latest);
+    assert_eq!(vec![0, 0, 4], sub.service_times);
+    assert_eq!(vec![(1, 2)], sub.precedences);
+}
+
+#[test]
+fn a_node_which_cannot_be_reached_is_reported() {
+    let inst = instance(&INSTANCE.replace("0 1000\n0 1000\n", "0 1000\n0 4\n"));
+    assert_eq!(vec![Conflict::Unreachable(5)], explain(&inst, |_| panic!("no solve expected")));
+}
+
+#[test]
+fn a_node_after_which_the_depot_cannot_be_reached_is_reported() {
+    let inst = instance(&INSTANCE.replace("0 1000\n5 12 4", "0 100\n5 12 4")
+        .replace("0 1000\n0 1000\n", "0 1000\n96 100\n"));
+    assert_eq!(vec![Conflict::Stranded(5)], obvious_conflicts(&inst));
+}
+
+#[test]
+fn nodes_which_must_precede_each_other_are_reported() {
+    let inst = instance(&INSTANCE.replace("5 12 4", "0 1000 4").replace("0 1000\n0 1000\n", "5 6\n5 6\n"));
+    assert_eq!(vec![Conflict::Cycle(4, 5)], obvious_conflicts(&inst));
+}
+
+#[test]
+fn a_minimal_infeasible_subset_is_found() {
+    let inst = instance(INSTANCE);
+    assert!(obvious_conflicts(&inst).is_empty());
+    let conflicts = explain(&inst, feasible);
+    assert_eq!(vec![Conflict::Subset{nodes: vec![1, 2, 3], minimal: true}], conflicts);
+    assert_eq!("nodes 1 2 3 cannot all be served", conflicts[0].to_string());
+}
+
+#[test]
+fn undecided_subsets_are_kept() {
+    let inst = instance(INSTANCE);
+    let conflicts = explain(&inst, |sub| if sub.nb_nodes == 5 { None } else { Some(false) });
+    assert_eq!(vec![Conflict::Subset{nodes: vec![1, 2, 3, 4, 5], minimal: false}], conflicts);
+}
@@ -0,0 +1,170 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{check::{check, evaluate}, instance::{End, Lateness, ParseError, PrecedenceError, TSPTWInstance, TimeWindow}, model::{Objective, TSPTW}, relax::Bound, tour::{Tour, TourError}};
+
+mod test_utils;
+use test_utils::{assert_optimal_on, random_distances, Lcg, OBJECTIVES};
+
+// The nodes lie on a line: the depot, customers 1 and 2 and the home of the
+// technician (node 3) which is far from the depot.
+const INSTANCE: &str = "4
+0  10 20 30
+10 0  10 20
+20 10 0  10
+30 20 10 0
+0 100
+0 100
+0 100
+0 100
+END 3
+";
+
+fn parse(text: &str) -> Result<TSPTWInstance, ParseError> {
+    TSPTWInstance::try_from(Cursor::new(text).lines())
+}
+
+#[test]
+fn the_end_of_the_tour_is_parsed() {
+    let inst = parse(INSTANCE).unwrap();
+    assert_eq!(End::Node(3), inst.end);
+    assert_eq!(2, inst.nb_customers());
+    assert_eq!(vec![1, 2], inst.customers().collect::<Vec<_>>());
+    assert!(!inst.is_customer(3));
+
+    assert_eq!(End::Free,  parse(&INSTANCE.replace("END 3", "END FREE")).unwrap().end);
+    assert_eq!(End::Depot, parse(&INSTANCE.replace("END 3", "END 0")).unwrap().end);
+    assert_eq!(End::Depot, parse(&INSTANCE.replace("END 3\n", "")).unwrap().end);
+}
+
+#[test]
+fn the_fleet_cannot_grow_when_the_tour_ends_elsewhere() {
+    let mut inst = parse(INSTANCE).unwrap();
+    match inst.set_fleet(2) {
+        Err(ParseError::IncompatibleFleet{fleet: 2}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    assert_eq!(1, inst.fleet);
+    assert!(inst.set_fleet(1).is_ok());
+
+    let mut inst = parse(&INSTANCE.replace("END 3\n", "")).unwrap();
+    assert!(inst.set_fleet(2).is_ok());
+    assert_eq!(2, inst.fleet);
+}
+
+#[test]
+fn invalid_ends_are_rejected() {
+    match parse(&INSTANCE.replace("END 3", "END 4")) {
+        Err(ParseError::BadEnd{line: 10, column: 5, ..}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("END 3", "END home")) {
+        Err(ParseError::BadEnd{line: 10, ..}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("END 3", "END FREE\nVEHICLES 2")) {
+        Err(ParseError::EndWithFleet{line: 10}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("END 3", "SHIFTS\n0 50\nEND 3")) {
+        Err(ParseError::EndWithFleet{line: 12}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("END 3", "END 3\nPRECEDENCES\n3 1")) {
+        Err(ParseError::InvalidPrecedence{line: 12, error: PrecedenceError::End(3)}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn a_path_is_built_from_a_permutation() {
+    let tour = Tour::from_path(4, End::Node(3), &[2, 1]).unwrap();
+    assert_eq!(&[0, 2, 1, 3], tour.route());
+    assert_eq!(&[2, 1], tour.customers());
+    assert_eq!(" 2 1 3", tour.to_string());
+    assert_eq!(tour, Tour::from_path(4, End::Node(3), &[0, 2, 1, 3]).unwrap());
+
+    let tour = Tour::from_path(4, End::Free, &[2, 3, 1]).unwrap();
+    assert!(tour.is_open());
+    assert_eq!(&[0, 2, 3, 1], tour.route());
+    assert_eq!(&[2, 3, 1], tour.customers());
+
+    assert_eq!(Err(TourError::EndInPermutation(3)), Tour::from_path(4, End::Node(3), &[3, 1]));
+    assert_eq!(Err(TourError::WrongLength{expected: 2, found: 3}), Tour::from_path(4, End::Node(3), &[1, 2, 0]));
+}
+
+#[test]
+fn a_path_is_evaluated_up_to_its_end() {
+    let inst = parse(INSTANCE).unwrap();
+    let eval = check(&inst, Cursor::new(" 1 2 3").lines()).unwrap();
+    assert!(eval.is_feasible());
+    assert_eq!(30, eval.tour_cost);
+    assert_eq!(30, eval.makespan);
+
+    let inst = parse(&INSTANCE.replace("END 3", "END FREE").replace("0 100\nEND", "0 100 5\nEND")).unwrap();
+    let eval = evaluate(&inst, &Tour::from_path(4, End::Free, &[1, 2, 3]).unwrap());
+    assert_eq!(30, eval.tour_cost);
+    assert_eq!(35, eval.makespan);
+}
+
+#[test]
+fn the_tour_ends_at_the_end_node() {
+    let inst = parse(INSTANCE).unwrap();
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&TSPTW::new(inst), Some(1), Some(1));
+    assert_eq!(30.0, value);
+    assert_eq!(&[0, 1, 2, 3], eval.unwrap().tour.route());
+
+    // The end node must be reached in time
+    let inst = parse(&INSTANCE.replace("0 100\nEND", "0 25\nEND")).unwrap();
+    assert_eq!(-1.0, test_utils::solve_instance(inst, Some(1), Some(1)));
+}
+
+#[test]
+fn the_tour_may_end_anywhere() {
+    let inst = parse(&INSTANCE.replace("END 3", "END FREE")).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::TravelTime);
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+    assert_eq!(30.0, value);
+    assert_eq!(&[0, 1, 2, 3], eval.unwrap().tour.route());
+
+    // Customer 1 opens late: the tour is better off ending there
+    let inst = parse(&INSTANCE.replace("END 3", "END FREE").replacen("0 100\n0 100", "0 100\n50 100", 1)).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::Makespan);
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+    assert_eq!(50.0, value);
+    assert_eq!(Some(&1), eval.unwrap().tour.route().last());
+}
+
+fn random_instance(rng: &mut Lcg, n: usize, end: End) -> TSPTWInstance {
+    let distances   = random_distances(rng, n);
+    let timewindows = (0..n)
+        .map(|i| if i == 0 {
+            TimeWindow::new(0, 100 + rng.next(100))
+        } else {
+            let earliest = rng.next(60);
+            TimeWindow::new(earliest, earliest + 10 + rng.next(40))
+        })
+        .collect();
+    let lateness = (0..n)
+        .map(|_| match rng.next(6) {
+            0 => Some(Lateness { penalty: 1 + rng.next(3), max: None }),
+            _ => None,
+        })
+        .collect();
+    let capacity = if rng.next(2) == 0 { None } else { Some(4 + rng.next(4)) };
+    TSPTWInstance {
+        lateness,
+        service_times: (0..n).map(|i| if i == 0 { 0 } else { rng.next(5) }).collect(),
+        loads        : (0..n).map(|i| if i == 0 { 0 } else { rng.next(3) as isize }).collect(),
+        capacity,
+        end,
+        ..TSPTWInstance::new(distances, timewindows)
+    }
+}
+
+#[test]
+fn the_optimum_is_found_on_random_instances() {
+    let mut rng = Lcg(5);
+    for end in [End::Node(6), End::Free].iter() {
+        assert_optimal_on(|| random_instance(&mut rng, 7, *end), 50, &OBJECTIVES, &[Bound::CheapestEdge]);
+    }
+}
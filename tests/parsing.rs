@@ -0,0 +1,150 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::instance::{ParseError, PrecedenceError, TSPTWInstance};
+
+fn parse(text: &str) -> Result<TSPTWInstance, ParseError> {
+    TSPTWInstance::try_from(Cursor::new(text).lines())
+}
+
+#[test]
+fn valid_instance_is_parsed() {
+    let inst = parse("# comment\n2\n0 1.5\n1.5 0\n0 10\n2 3\n# trailer\n").unwrap();
+    assert_eq!(2, inst.nb_nodes);
+    assert_eq!(1, inst.precision);
+    assert_eq!(15, inst.distances[(0, 1)]);
+    assert_eq!(20, inst.timewindows[1].earliest);
+    assert_eq!(30, inst.timewindows[1].latest);
+}
+
+#[test]
+fn precision_is_detected_from_significant_decimals() {
+    let inst = parse("2\n0.000 79.4\n0.1 0\n0 10.50\n0 10\n").unwrap();
+    assert_eq!(1, inst.precision);
+    assert_eq!(794, inst.distances[(0, 1)]);
+    assert_eq!(1, inst.distances[(1, 0)]);
+    assert_eq!(105, inst.timewindows[0].latest);
+
+    let inst = parse("2\n0 7\n7 0\n0 10\n0 10\n").unwrap();
+    assert_eq!(0, inst.precision);
+    assert_eq!(7, inst.distances[(0, 1)]);
+}
+
+#[test]
+fn precision_can_be_overridden() {
+    let mut inst = parse("2\n0 1.25\n1.35 0\n0 10\n0 10\n").unwrap();
+    assert_eq!(2, inst.precision);
+
+    inst.set_precision(4);
+    assert_eq!(12500, inst.distances[(0, 1)]);
+    assert_eq!(100000, inst.timewindows[0].latest);
+    assert_eq!("1.2500", inst.format(12500));
+
+    inst.set_precision(1);
+    assert_eq!(13, inst.distances[(0, 1)]);
+    assert_eq!(14, inst.distances[(1, 0)]);
+    assert_eq!(100, inst.timewindows[0].latest);
+}
+
+#[test]
+fn empty_file_has_no_node_count() {
+    match parse("# nothing here\n") {
+        Err(ParseError::BadNodeCount{line: 2, column: 1, ..}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn bad_node_count_is_reported() {
+    match parse("  two\n") {
+        Err(ParseError::BadNodeCount{line: 1, column: 3, token}) => assert_eq!("two", token),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn short_matrix_row_is_reported() {
+    match parse("2\n0 1\n1\n0 10\n0 10\n") {
+        Err(ParseError::WrongRowLength{line: 3, expected: 2, found: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn missing_matrix_row_is_reported() {
+    match parse("2\n0 1\n") {
+        Err(ParseError::MissingMatrixRow{line: 3, node: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn missing_time_window_is_reported() {
+    match parse("2\n0 1\n1 0\n0 10\n") {
+        Err(ParseError::MissingTimeWindow{line: 5, node: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse("2\n0 1\n1 0\n0 10\n5\n") {
+        Err(ParseError::MissingTimeWindow{line: 5, node: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn extra_time_window_is_rejected() {
+    match parse("2\n0 1\n1 0\n0 10\n0 10\n0 10\n") {
+        Err(ParseError::UnexpectedLine{line: 6}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse("2\n0 1\n1 0\n0 10\n0 10\nCAPACITY 3\n1 2\n") {
+        Err(ParseError::UnexpectedLine{line: 7}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn keywords_must_be_whole_tokens() {
+    for line in ["ENDPOINT 1", "VEHICLES2 3", "CAPACITY: 3"].iter() {
+        match parse(&format!("2\n0 1\n1 0\n0 10\n0 10\n{}\n", line)) {
+            Err(ParseError::UnexpectedLine{line: 6}) => (),
+            other => panic!("unexpected outcome {:?}", other.err()),
+        }
+    }
+}
+
+#[test]
+fn non_numeric_token_is_reported() {
+    match parse("2\n0 1\n1 0\n0 10\n0 abc\n") {
+        Err(ParseError::NotANumber{line: 5, column: 3, token}) => assert_eq!("abc", token),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn precedences_are_parsed() {
+    let inst = parse("3\n0 1 1\n1 0 1\n1 1 0\n0 10\n0 10\n0 10\nPRECEDENCES\n2 1\n").unwrap();
+    assert_eq!(vec![(2, 1)], inst.precedences);
+    assert_eq!(3, inst.timewindows.len());
+}
+
+#[test]
+fn cyclic_precedences_are_rejected() {
+    let text = "4\n0 1 1 1\n1 0 1 1\n1 1 0 1\n1 1 1 0\n0 10\n0 10\n0 10\n0 10\nPRECEDENCES\n1 2\n2 3\n3 1\n";
+    match parse(text) {
+        Err(ParseError::InvalidPrecedence{line: 13, error: PrecedenceError::Cycle(nodes)}) => 
+            assert_eq!(vec![1, 2, 3], nodes),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn precedences_must_involve_customers() {
+    let text = "3\n0 1 1\n1 0 1\n1 1 0\n0 10\n0 10\n0 10\nPRECEDENCES\n1 3\n";
+    match parse(text) {
+        Err(ParseError::InvalidPrecedence{line: 9, error: PrecedenceError::UnknownNode(3)}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&text.replace("1 3\n", "0 1\n")) {
+        Err(ParseError::InvalidPrecedence{line: 9, error: PrecedenceError::Depot}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
@@ -0,0 +1,109 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use ddo::{Problem, Variable};
+use tsptw::{check::evaluate, instance::{PrecedenceError, TSPTWInstance}, model::TSPTW, precedence::PrecedenceGraph, tour::Tour};
+
+mod test_utils;
+
+// Node 1 opens at 20 and node 2 closes at 15: node 2 can never be visited
+// after node 1.
+const INSTANCE: &str = "3
+0  10 10
+10 0  10
+10 10 0
+0 100
+20 50
+0 15
+";
+
+// The direct arc from 1 to 2 is too long, but going through node 3 is not.
+const DETOUR: &str = "4
+0  10  10 10
+10 0  100  5
+10 100 0   5
+10 5   5   0
+0 200
+20 50
+0 50
+0 100
+";
+
+fn instance(text: &str) -> TSPTWInstance {
+    TSPTWInstance::try_from(Cursor::new(text).lines()).unwrap()
+}
+
+#[test]
+fn late_arcs_are_forbidden_and_imply_precedences() {
+    let graph = PrecedenceGraph::new(&instance(INSTANCE));
+    assert!(graph.is_forbidden(1, 2));
+    assert!(!graph.is_forbidden(2, 1));
+    assert!(graph.must_precede(2, 1));
+    assert!(!graph.must_precede(1, 2));
+    assert_eq!(1, graph.nb_forbidden_arcs());
+    assert_eq!(1, graph.nb_precedences());
+}
+
+#[test]
+fn precedences_account_for_detours() {
+    let graph = PrecedenceGraph::new(&instance(DETOUR));
+    assert!(graph.is_forbidden(1, 2));
+    assert_eq!(10, graph.shortest(1, 2));
+    assert!(!graph.must_precede(2, 1));
+}
+
+#[test]
+fn nodes_are_only_reachable_after_their_predecessors() {
+    let pb     = TSPTW::new(instance(INSTANCE)).with_preprocessing(false);
+    let root   = pb.initial_state();
+    let domain = pb.domain_of(&root, Variable(0)).into_iter().collect::<Vec<_>>();
+    assert_eq!(vec![2], domain);
+}
+
+#[test]
+fn optimal_tours_respect_the_precedences() {
+    for id in ["AFG/rbg016a.tw", "AFG/rbg020a.tw", "Langevin/N40ft401.dat"].iter() {
+        let pb = TSPTW::new(test_utils::read(id));
+        assert!(pb.precedences.nb_precedences() > 0, "{}", id);
+
+        let (_, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(10), Some(1));
+        let route     = eval.unwrap().tour.route().to_vec();
+        for (k, &j) in route.iter().enumerate() {
+            for &i in route[k + 1..].iter().filter(|i| **i != 0) {
+                assert!(!pb.precedences.must_precede(i, j), "{}: {} before {}", id, i, j);
+            }
+        }
+    }
+}
+
+#[test]
+fn cycles_cannot_be_added_through_the_api() {
+    let mut inst = instance(DETOUR);
+    inst.add_precedence(1, 2).unwrap();
+    inst.add_precedence(2, 3).unwrap();
+    assert_eq!(Err(PrecedenceError::Cycle(vec![1, 2, 3])), inst.add_precedence(3, 1));
+    assert_eq!(Err(PrecedenceError::Cycle(vec![2])), inst.add_precedence(2, 2));
+    assert_eq!(vec![(1, 2), (2, 3)], inst.precedences);
+}
+
+#[test]
+fn given_precedences_are_enforced() {
+    let mut inst = test_utils::read("AFG/rbg016a.tw");
+    let free     = test_utils::solve_and_evaluate_problem(&TSPTW::new(inst.clone()), Some(10), Some(1)).1.unwrap();
+    // Reverse the order of the first two customers of the optimal tour
+    let first    = free.tour.customers()[0];
+    let second   = free.tour.customers()[1];
+    inst.add_precedence(second, first).unwrap();
+    assert_eq!(vec![(second, first)], evaluate(&inst, &free.tour).inversions);
+    assert!(!evaluate(&inst, &free.tour).is_feasible());
+
+    let pb             = TSPTW::new(inst.clone());
+    let (value, eval)  = test_utils::solve_and_evaluate_problem(&pb, Some(10), Some(1));
+    let eval           = eval.unwrap();
+    let route          = eval.tour.customers();
+    assert!(eval.is_feasible());
+    assert!(route.iter().position(|x| *x == second) < route.iter().position(|x| *x == first));
+    assert!(value >= test_utils::solve_instance(test_utils::read("AFG/rbg016a.tw"), Some(10), Some(1)));
+
+    let tour = Tour::from_permutation(inst.nb_nodes as usize, route).unwrap();
+    assert!(evaluate(&inst, &tour).inversions.is_empty());
+}
@@ -0,0 +1,69 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{instance::TSPTWInstance, model::TSPTW, preprocess::tighten};
+
+mod test_utils;
+
+// Node 2 must be visited first: it closes at 15 and cannot be reached in 
+// time after node 1. Hence the tour is 0 -> 2 -> 1 -> 0 and the windows can
+// be narrowed to the times at which that tour visits each node.
+const INSTANCE: &str = "3
+0  10 10
+10 0  10
+10 10 0
+0 100
+0 50
+0 15
+";
+
+fn instance() -> TSPTWInstance {
+    TSPTWInstance::try_from(Cursor::new(INSTANCE).lines()).unwrap()
+}
+fn windows(inst: &TSPTWInstance) -> Vec<(usize, usize)> {
+    inst.timewindows.iter().map(|tw| (tw.earliest, tw.latest)).collect()
+}
+
+#[test]
+fn windows_are_tightened_to_a_fixpoint() {
+    let mut inst = instance();
+    let report   = tighten(&mut inst);
+    assert!(report.feasible);
+    assert_eq!(vec![(20, 30), (10, 20), (10, 10)], windows(&inst));
+    assert_eq!(vec![0, 1, 2], report.tightened());
+    assert_eq!(90 + 40 + 15, report.total_shrinkage());
+
+    // tightening again changes nothing
+    let again = tighten(&mut inst);
+    assert_eq!(0, again.total_shrinkage());
+    assert_eq!(1, again.rounds);
+}
+
+#[test]
+fn infeasible_instances_are_left_untouched() {
+    let text     = INSTANCE.replace("0 50\n0 15", "0 5\n0 5");
+    let mut inst = TSPTWInstance::try_from(Cursor::new(text).lines()).unwrap();
+    let report   = tighten(&mut inst);
+    assert!(!report.feasible);
+    assert_eq!(vec![(0, 100), (0, 5), (0, 5)], windows(&inst));
+}
+
+#[test]
+fn preprocessing_is_enabled_by_default_and_can_be_disabled() {
+    let pb = TSPTW::new(instance());
+    assert!(pb.tightening.is_some());
+    assert_eq!(vec![(20, 30), (10, 20), (10, 10)], windows(&pb.instance));
+
+    let pb = pb.with_preprocessing(false);
+    assert!(pb.tightening.is_none());
+    assert_eq!(windows(&instance()), windows(&pb.instance));
+}
+
+#[test]
+fn preprocessing_preserves_the_optimum() {
+    for id in ["AFG/rbg010a.tw", "AFG/rbg016a.tw", "Langevin/N40ft401.dat"].iter() {
+        let tight = TSPTW::new(test_utils::read(id));
+        let loose = TSPTW::new(test_utils::read(id)).with_preprocessing(false);
+        assert_eq!(test_utils::solve_problem(&loose, Some(10), Some(1)),
+                   test_utils::solve_problem(&tight, Some(10), Some(1)), "{}", id);
+    }
+}
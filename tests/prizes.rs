@@ -0,0 +1,156 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{check::{check, evaluate}, instance::{End, Lateness, ParseError, PrecedenceError, TSPTWInstance, TimeWindow}, model::{Objective, TSPTW}, relax::Bound, tour::{Tour, TourError}};
+
+mod test_utils;
+use test_utils::{assert_optimal_on, random_distances, Lcg, OBJECTIVES};
+
+// The depot lies between customers 1 and 2. Customer 3 is optional: it lies
+// far beyond customer 2 and serving it costs 80 more than leaving it out.
+const INSTANCE: &str = "4
+0  10 10 50
+10 0  20 60
+10 20 0  40
+50 60 40 0
+0 200
+0 200
+0 200
+0 200
+PRIZES
+3 30
+";
+
+fn parse(text: &str) -> Result<TSPTWInstance, ParseError> {
+    TSPTWInstance::try_from(Cursor::new(text).lines())
+}
+
+#[test]
+fn prizes_are_parsed() {
+    let inst = parse(INSTANCE).unwrap();
+    assert_eq!(vec![None, None, None, Some(30)], inst.prizes);
+    assert!(inst.is_optional(3));
+    assert!(!inst.is_optional(1));
+    assert_eq!(30, inst.total_prize());
+
+    let inst = parse(&INSTANCE.replace("3 30\n", "3 2.5\n")).unwrap();
+    assert_eq!(Some(25), inst.prizes[3]);
+    assert_eq!(100, inst.distances[(0, 1)]);
+
+    let inst = parse(&INSTANCE.replace("PRIZES\n3 30\n", "")).unwrap();
+    assert!(!inst.has_prizes());
+}
+
+#[test]
+fn invalid_prizes_are_rejected() {
+    match parse(&INSTANCE.replace("3 30\n", "0 30\n")) {
+        Err(ParseError::BadPrize{line: 11, node: 0}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("3 30\n", "3 30\nEND 3\n")) {
+        Err(ParseError::BadPrize{line: 11, node: 3}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("3 30\n", "7 30\n")) {
+        Err(ParseError::UnknownNode{line: 11, node: 7}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("3 30\n", "3\n")) {
+        Err(ParseError::WrongRowLength{line: 11, expected: 2, found: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("3 30\n", "3 30\nPRECEDENCES\n1 3\n")) {
+        Err(ParseError::InvalidPrecedence{line: 13, error: PrecedenceError::Optional(3)}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn optional_customers_may_be_left_out() {
+    let inst = parse(INSTANCE).unwrap();
+    let tour = Tour::from_sequence(&inst, &[2, 1]).unwrap();
+    assert_eq!(&[0, 2, 1, 0], tour.route());
+    assert_eq!(tour, Tour::from_sequence(&inst, &[0, 2, 1, 0]).unwrap());
+
+    let eval = evaluate(&inst, &tour);
+    assert!(eval.is_feasible());
+    assert_eq!(40, eval.tour_cost);
+    assert_eq!(30, eval.forfeited);
+
+    let eval = check(&inst, Cursor::new(" 1 2 3 0").lines()).unwrap();
+    assert_eq!(120, eval.tour_cost);
+    assert_eq!(0, eval.forfeited);
+
+    // Only the optional customers may be left out
+    assert_eq!(Err(TourError::WrongLength{expected: 3, found: 2}), Tour::from_sequence(&inst, &[1]));
+    assert_eq!(Err(TourError::WrongLength{expected: 3, found: 4}), Tour::from_sequence(&inst, &[1, 3, 2, 3]));
+}
+
+#[test]
+fn the_solver_trades_prizes_against_travel() {
+    let inst = parse(INSTANCE).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::TravelTime);
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+    assert_eq!(70.0, value);
+    assert!(!eval.unwrap().tour.route().contains(&3));
+
+    let inst = parse(&INSTANCE.replace("3 30\n", "3 100\n")).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::TravelTime);
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+    assert_eq!(120.0, value);
+    assert!(eval.unwrap().tour.route().contains(&3));
+
+    // Customer 3 can no longer be served before the depot closes
+    let inst = parse(&INSTANCE.replacen("0 200", "0 100", 1).replace("3 30\n", "3 100\n")).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::TravelTime);
+    assert_eq!(140.0, test_utils::solve_problem(&pb, Some(1), Some(1)));
+}
+
+#[test]
+fn the_tour_may_stay_at_the_depot() {
+    let inst = parse(&INSTANCE.replace("3 30\n", "1 5\n2 5\n3 5\n")).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::TravelTime);
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+    assert_eq!(15.0, value);
+    assert_eq!(&[0, 0], eval.unwrap().tour.route());
+}
+
+fn random_instance(rng: &mut Lcg, n: usize, end: End, fleet: usize) -> TSPTWInstance {
+    let distances   = random_distances(rng, n);
+    let timewindows = (0..n)
+        .map(|i| if i == 0 {
+            TimeWindow::new(0, 60 + rng.next(100))
+        } else {
+            let earliest = rng.next(60);
+            TimeWindow::new(earliest, earliest + 5 + rng.next(40))
+        })
+        .collect();
+    let lateness = (0..n)
+        .map(|_| match rng.next(6) {
+            0 => Some(Lateness { penalty: 1 + rng.next(3), max: None }),
+            _ => None,
+        })
+        .collect();
+    // About half of the customers are optional
+    let prizes = (0..n)
+        .map(|i| if i == 0 || end == End::Node(i) || rng.next(2) == 0 { None } else { Some(rng.next(40)) })
+        .collect();
+    let capacity = if rng.next(2) == 0 { None } else { Some(4 + rng.next(4)) };
+    TSPTWInstance {
+        lateness,
+        service_times: (0..n).map(|i| if i == 0 { 0 } else { rng.next(5) }).collect(),
+        loads        : (0..n).map(|i| if i == 0 { 0 } else { rng.next(3) as isize }).collect(),
+        capacity,
+        fleet,
+        end,
+        prizes,
+        ..TSPTWInstance::new(distances, timewindows)
+    }
+}
+
+#[test]
+fn the_optimum_is_found_on_random_instances() {
+    let mut rng = Lcg(13);
+    for (end, fleet) in [(End::Depot, 1), (End::Node(6), 1), (End::Free, 1), (End::Depot, 2)].iter().copied() {
+        assert_optimal_on(|| random_instance(&mut rng, 7, end, fleet), 25, &OBJECTIVES, &[Bound::CheapestEdge, Bound::SpanningTree]);
+    }
+}
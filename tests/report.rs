@@ -0,0 +1,102 @@
+use tsptw::report::{Format, Record, Visit};
+
+fn record() -> Record {
+    Record {
+        instance   : "AFG/rbg010a.tw".to_string(),
+        status     : "Proved".to_string(),
+        upper_bound: Some(671.0),
+        lower_bound: Some(671.0),
+        gap        : Record::gap(Some(671.0), Some(671.0)),
+        duration   : 0.25,
+        threads    : 1,
+        width      : Some(2),
+        tour       : Some(vec![0, 2, 1, 0]),
+        schedule   : Some(vec![
+            Visit{node: 0, arrival:  0.0, wait: 0.0, start:  0.0, departure:  0.0},
+            Visit{node: 2, arrival:  5.0, wait: 0.0, start:  5.0, departure:  5.0},
+            Visit{node: 1, arrival: 10.0, wait: 2.0, start: 12.0, departure: 22.0},
+            Visit{node: 0, arrival: 27.0, wait: 0.0, start: 27.0, departure: 27.0},
+        ]),
+        precision  : 0,
+    }
+}
+
+#[test]
+fn gap_is_relative_to_the_upper_bound() {
+    assert_eq!(Some(0.5), Record::gap(Some(10.0), Some(5.0)));
+    assert_eq!(None, Record::gap(None, Some(5.0)));
+    assert_eq!(None, Record::gap(Some(10.0), None));
+}
+
+#[test]
+fn json_record_can_be_read_back() {
+    let json = record().render(Format::Json);
+    assert!(!json.contains('\n'));
+    assert_eq!(record(), serde_json::from_str::<Record>(&json).unwrap());
+}
+
+#[test]
+fn csv_record_has_one_field_per_column() {
+    let csv = record().render(Format::Csv);
+    assert_eq!(Format::Csv.header().unwrap().split(',').count(), csv.split(',').count());
+    assert!(csv.ends_with(",0 2 1 0,0 5 10 27,0 0 2 0"));
+}
+
+#[test]
+fn table_record_keeps_the_historical_layout() {
+    let table = record().render(Format::Table);
+    assert!(table.starts_with("AFG/rbg010a.tw "));
+    assert!(table.ends_with("|  2 1 0"));
+
+    let mut infeasible = record();
+    infeasible.upper_bound = None;
+    infeasible.tour        = None;
+    assert!(infeasible.render(Format::Table).contains("+inf"));
+    assert!(infeasible.render(Format::Table).ends_with("No feasible solution found"));
+}
+
+#[test]
+fn records_can_be_parsed_back() {
+    let json = record().render(Format::Json);
+    assert_eq!(Some(record()), Record::parse(&json, Format::Json));
+
+    let csv = Record::parse(&record().render(Format::Csv), Format::Csv).unwrap();
+    assert_eq!(record().tour, csv.tour);
+    assert_eq!(record().upper_bound, csv.upper_bound);
+    assert_eq!(record().width, csv.width);
+
+    let table = Record::parse(&record().render(Format::Table), Format::Table).unwrap();
+    assert_eq!(record().instance, table.instance);
+    assert_eq!(record().status, table.status);
+    assert_eq!(record().tour, table.tour);
+
+    for format in [Format::Table, Format::Csv].iter().copied() {
+        assert_eq!(None, Record::parse(&format.header().unwrap(), format));
+    }
+}
+
+#[test]
+fn bounds_are_printed_with_the_precision_of_the_instance() {
+    let mut rec = record();
+    rec.upper_bound = Some(917.8265000000001);
+    rec.lower_bound = Some(900.5);
+    rec.precision   = 4;
+    for format in [Format::Table, Format::Csv].iter().copied() {
+        let line = rec.render(format);
+        assert!(line.contains("917.8265 ") || line.contains("917.8265,"), "{}", line);
+        assert!(line.contains("900.5000"), "{}", line);
+        assert_eq!(4, Record::parse(&line, format).unwrap().precision);
+    }
+}
+
+#[test]
+fn csv_fields_are_quoted_when_needed() {
+    let mut rec  = record();
+    rec.instance = "odd,\"name\".tw".to_string();
+    let csv      = rec.render(Format::Csv);
+    assert!(csv.starts_with("\"odd,\"\"name\"\".tw\",Proved,"));
+    assert_eq!(rec.instance, Record::parse(&csv, Format::Csv).unwrap().instance);
+
+    assert!(record().render(Format::Csv).starts_with("AFG/rbg010a.tw,Proved,671,671,"));
+    assert_eq!(None, Record::parse("\"AFG/rbg010a.tw,Proved,671,671,0,0.250,1,2,0 2 1 0,,", Format::Csv));
+}
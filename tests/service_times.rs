@@ -0,0 +1,51 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{instance::TSPTWInstance, model::{Objective, TSPTW}, schedule::{schedule, Stop}};
+
+mod test_utils;
+
+// Going 0 -> 1 -> 2 -> 0 is much shorter, but the service at node 1 makes it
+// impossible to reach node 2 before its deadline.
+const INSTANCE: &str = "3
+0 1 5
+5 0 1
+1 5 0
+0  100
+12 100 10
+0  5
+";
+
+fn instance() -> TSPTWInstance {
+    TSPTWInstance::try_from(Cursor::new(INSTANCE).lines()).unwrap()
+}
+
+#[test]
+fn service_times_are_parsed() {
+    let inst = instance();
+    assert_eq!(vec![0, 10, 0], inst.service_times);
+}
+
+#[test]
+fn service_time_delays_the_next_arrival() {
+    assert_eq!(17.0, test_utils::solve_instance(instance(), None, Some(1)));
+}
+
+#[test]
+fn schedule_distinguishes_arrival_start_and_departure() {
+    let stops = schedule(&instance(), &[0, 2, 1, 0]);
+    assert_eq!(Stop{node: 0, arrival:  0, start:  0, departure:  0}, stops[0]);
+    assert_eq!(Stop{node: 2, arrival:  5, start:  5, departure:  5}, stops[1]);
+    assert_eq!(Stop{node: 1, arrival: 10, start: 12, departure: 22}, stops[2]);
+    assert_eq!(Stop{node: 0, arrival: 27, start: 27, departure: 27}, stops[3]);
+    assert_eq!(2, stops[2].waiting());
+}
+
+#[test]
+fn objective_is_selectable() {
+    let pb = TSPTW::new(instance()).with_objective(Objective::TravelTime);
+    assert_eq!(15.0, test_utils::solve_problem(&pb, None, Some(1)));
+    let pb = TSPTW::new(instance()).with_objective(Objective::Makespan);
+    assert_eq!(27.0, test_utils::solve_problem(&pb, None, Some(1)));
+    let pb = TSPTW::new(instance()).with_objective(Objective::TravelAndWaiting);
+    assert_eq!(17.0, test_utils::solve_problem(&pb, None, Some(1)));
+}
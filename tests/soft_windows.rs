@@ -0,0 +1,131 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{check::evaluate, instance::{Lateness, ParseError, TSPTWInstance, TimeWindow}, model::TSPTW, relax::Bound, tour::Tour};
+
+mod test_utils;
+use test_utils::{assert_optimal_on, random_distances, Lcg, OBJECTIVES};
+
+// The nodes lie on a line. Node 2 cannot be served in time: whatever the
+// tour, the salesman reaches it at time 20 (or later)
+const INSTANCE: &str = "3
+0  10 20
+10 0  10
+20 10 0
+0 100
+0 10
+0 15
+LATENESS
+2 2
+";
+
+fn parse(text: &str) -> Result<TSPTWInstance, ParseError> {
+    TSPTWInstance::try_from(Cursor::new(text).lines())
+}
+
+#[test]
+fn lateness_is_parsed() {
+    let inst = parse(INSTANCE).unwrap();
+    assert_eq!(vec![None, None, Some(Lateness{penalty: 2, max: None})], inst.lateness);
+    assert_eq!(usize::MAX, inst.deadline(2));
+    assert_eq!(10, inst.deadline(1));
+    assert!(inst.has_soft_windows());
+
+    let inst = parse(&INSTANCE.replace("2 2\n", "2 2 4.5\n")).unwrap();
+    assert_eq!(Some(Lateness{penalty: 2, max: Some(45)}), inst.lateness[2]);
+    assert_eq!(195, inst.deadline(2));
+
+    let inst = parse(&INSTANCE.replace("LATENESS\n2 2\n", "")).unwrap();
+    assert!(!inst.has_soft_windows());
+}
+
+#[test]
+fn invalid_lateness_is_rejected() {
+    match parse(&INSTANCE.replace("2 2\n", "7 2\n")) {
+        Err(ParseError::UnknownNode{line: 9, node: 7}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("2 2\n", "2\n")) {
+        Err(ParseError::WrongRowLength{line: 9, expected: 3, found: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("2 2\n", "2 -1\n")) {
+        Err(ParseError::NotANumber{line: 9, ..}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn late_services_are_penalized() {
+    let inst = parse(INSTANCE).unwrap();
+    assert_eq!(Some(20), inst.next_opening(2, 20));
+    assert_eq!(10,       inst.lateness_cost(2, 20));
+    assert_eq!(0,        inst.lateness_cost(2, 15));
+
+    let eval = evaluate(&inst, &Tour::from_permutation(3, &[1, 2]).unwrap());
+    assert!(eval.is_feasible());
+    assert_eq!(10, eval.penalty);
+
+    let eval = evaluate(&inst, &Tour::from_permutation(3, &[2, 1]).unwrap());
+    assert_eq!(vec![1], eval.violations);
+
+    let inst = parse(&INSTANCE.replace("LATENESS\n2 2\n", "")).unwrap();
+    let eval = evaluate(&inst, &Tour::from_permutation(3, &[1, 2]).unwrap());
+    assert_eq!(vec![2], eval.violations);
+    assert_eq!(0, eval.penalty);
+}
+
+#[test]
+fn the_solver_pays_the_penalty_when_it_must() {
+    let inst = parse(INSTANCE).unwrap();
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&TSPTW::new(inst), Some(1), Some(1));
+    assert_eq!(50.0, value);
+    assert_eq!(vec![1, 2], eval.unwrap().tour.customers());
+
+    let inst = parse(&INSTANCE.replace("LATENESS\n2 2\n", "")).unwrap();
+    assert_eq!(-1.0, test_utils::solve_instance(inst, Some(1), Some(1)));
+}
+
+#[test]
+fn the_maximum_lateness_is_a_hard_constraint() {
+    let inst = parse(&INSTANCE.replace("2 2\n", "2 2 4\n")).unwrap();
+    assert_eq!(-1.0, test_utils::solve_instance(inst, Some(1), Some(1)));
+
+    let mut inst = parse(&INSTANCE.replace("LATENESS\n2 2\n", "")).unwrap();
+    inst.soften(1, Some(5.0));
+    assert_eq!(Some(Lateness{penalty: 1, max: Some(5)}), inst.lateness[2]);
+    assert_eq!(45.0, test_utils::solve_instance(inst, Some(1), Some(1)));
+}
+
+fn random_instance(rng: &mut Lcg, n: usize) -> TSPTWInstance {
+    let distances = random_distances(rng, n);
+    // The windows are too narrow for most tours: about one node out of
+    // four has a hard window and the others may be served late
+    let timewindows = (0..n)
+        .map(|i| if i == 0 {
+            TimeWindow::new(0, 100 + rng.next(100))
+        } else {
+            let earliest = rng.next(60);
+            TimeWindow::new(earliest, earliest + 5 + rng.next(20))
+        })
+        .collect();
+    let lateness = (0..n)
+        .map(|_| match rng.next(4) {
+            0 => None,
+            _ => Some(Lateness {
+                penalty: 1 + rng.next(3),
+                max    : if rng.next(2) == 0 { None } else { Some(rng.next(60)) },
+            }),
+        })
+        .collect();
+    TSPTWInstance {
+        lateness,
+        service_times: (0..n).map(|i| if i == 0 { 0 } else { rng.next(5) }).collect(),
+        ..TSPTWInstance::new(distances, timewindows)
+    }
+}
+
+#[test]
+fn the_optimum_is_found_on_random_instances() {
+    let mut rng = Lcg(7);
+    assert_optimal_on(|| random_instance(&mut rng, 7), 100, &OBJECTIVES, &[Bound::CheapestEdge]);
+}
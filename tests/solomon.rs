@@ -0,0 +1,85 @@
+use std::io::{BufRead, Cursor};
+
+use tsptw::{instance::{ParseError, TSPTWInstance}, solomon::{Rounding, SolomonReader}};
+
+const C101_EXCERPT: &str = "C101
+
+VEHICLE
+NUMBER     CAPACITY
+  25         200
+
+CUSTOMER
+CUST NO.  XCOORD.   YCOORD.    DEMAND   READY TIME  DUE DATE   SERVICE   TIME
+ 
+    0      40         50          0          0       1236          0   
+    1      45         68         10        912        967         90   
+    2      45         70         30        825        870         90   
+";
+
+fn read(text: &str, reader: SolomonReader) -> Result<TSPTWInstance, ParseError> {
+    reader.read(Cursor::new(text).lines())
+}
+
+#[test]
+fn service_time_is_folded_into_outgoing_edges() {
+    let inst = read(C101_EXCERPT, SolomonReader::default()).unwrap();
+    assert_eq!(3, inst.nb_nodes);
+    assert_eq!(4, inst.precision);
+    // sqrt(5^2 + 18^2) = 18.681541...
+    assert_eq!(186815, inst.distances[(0, 1)]);
+    assert_eq!(186815 + 900000, inst.distances[(1, 0)]);
+    assert_eq!(900000, inst.distances[(1, 1)]);
+    assert_eq!(9120000, inst.timewindows[1].earliest);
+    assert_eq!(9670000, inst.timewindows[1].latest);
+}
+
+#[test]
+fn rounding_convention_is_selectable() {
+    let inst = read(C101_EXCERPT, SolomonReader::new(Rounding::Truncate, 1)).unwrap();
+    assert_eq!(186, inst.distances[(0, 1)]);
+    let inst = read(C101_EXCERPT, SolomonReader::new(Rounding::Nearest, 1)).unwrap();
+    assert_eq!(187, inst.distances[(0, 1)]);
+    let inst = read(C101_EXCERPT, SolomonReader::new(Rounding::Nearest, 0)).unwrap();
+    assert_eq!(19, inst.distances[(0, 1)]);
+    assert_eq!(912, inst.timewindows[1].earliest);
+}
+
+#[test]
+fn short_customer_line_is_reported() {
+    let text = format!("{}    3      45\n", C101_EXCERPT);
+    match read(&text, SolomonReader::default()) {
+        Err(ParseError::WrongRowLength{line: 13, expected: 7, found: 2}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn misnumbered_customer_is_reported() {
+    let text = C101_EXCERPT.replace("    2      45", "    3      45");
+    match read(&text, SolomonReader::default()) {
+        Err(ParseError::MisnumberedNode{line: 12, expected: 2, found}) => assert_eq!("3", found),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    let text = C101_EXCERPT.replace("    0      40", "    1      40");
+    match read(&text, SolomonReader::default()) {
+        Err(ParseError::MisnumberedNode{line: 10, expected: 0, ..}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn missing_customer_section_is_reported() {
+    match read("C101\n\nVEHICLE\n", SolomonReader::default()) {
+        Err(ParseError::BadNodeCount{..}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn service_time_can_be_kept_apart() {
+    let reader = SolomonReader::default().with_fold_service(false);
+    let inst   = read(C101_EXCERPT, reader).unwrap();
+    assert_eq!(186815, inst.distances[(1, 0)]);
+    assert_eq!(0, inst.distances[(1, 1)]);
+    assert_eq!(vec![0, 900000, 900000], inst.service_times);
+}
@@ -1,7 +1,9 @@
-use std::{fs::File, path::PathBuf, time::Duration};
+#![allow(dead_code)]
 
-use ddo::{NbUnassignedWitdh, NoDupFrontier, ParallelSolver, Solver, TimeBudget, Times, config_builder};
-use tsptw::{instance::TSPTWInstance, model::TSPTW, relax::TSPTWRelax};
+use std::{convert::TryFrom, fs::File, path::PathBuf};
+
+use ddo::{Matrix, Solver};
+use tsptw::{check::{evaluate, Evaluation}, instance::TSPTWInstance, model::{Objective, TSPTW}, relax::{Bound, TSPTWRelax}, solver, tour::Tour};
 
 
 
@@ -12,35 +14,154 @@ fn locate(id: &str) -> PathBuf {
         .join(id)
 }
 
+/// No test may run longer than this (in seconds)
 const TIMEOUT : u64 = 95;
-fn mk_solver<'a, 'b>(pb: &'a TSPTW, relax: TSPTWRelax<'a>, width: Option<usize>, threads: Option<usize>) -> Box<dyn Solver + 'a> {
-    if let Some(w) = width {
-        let mdd = config_builder(pb, relax)
-            .with_max_width(Times(w, NbUnassignedWitdh))
-            .with_cutoff(TimeBudget::new(Duration::from_secs(TIMEOUT)))
-            .into_deep();
-        let solver = ParallelSolver::new(mdd)
-            .with_nb_threads(threads.unwrap_or(num_cpus::get()))
-            .with_frontier(NoDupFrontier::default());
-        Box::new(solver)
-    } else {
-        let conf = config_builder(pb, relax)
-            .with_cutoff(TimeBudget::new(Duration::from_secs(TIMEOUT)));
-        let mdd  = conf.into_deep();
-        let solver = ParallelSolver::new(mdd)
-            .with_nb_threads(threads.unwrap_or(num_cpus::get()))
-            .with_frontier(NoDupFrontier::default());
-        Box::new(solver)
-    }
+fn mk_solver<'a>(pb: &'a TSPTW, relax: TSPTWRelax<'a>, width: Option<usize>, threads: Option<usize>) -> Box<dyn Solver + 'a> {
+    solver::mk_solver(pb, relax, None, width, threads, Some(TIMEOUT))
 }
 
-pub fn solve(instance: &str, width: Option<usize>, threads: Option<usize>) -> f32 {
+pub fn read(instance: &str) -> TSPTWInstance {
    let file       = File::open(locate(instance)).expect("file not found");
-   let inst       = TSPTWInstance::from(file);
-   let pb         = TSPTW::new(inst);
-   let relax      = TSPTWRelax::new(&pb);
-   let mut solver = mk_solver(&pb, relax, width, threads);
+   TSPTWInstance::try_from(file).expect("invalid instance")
+}
+
+pub fn solve(instance: &str, width: Option<usize>, threads: Option<usize>) -> f32 {
+   solve_instance(read(instance), width, threads)
+}
+
+pub fn solve_instance(inst: TSPTWInstance, width: Option<usize>, threads: Option<usize>) -> f32 {
+   solve_problem(&TSPTW::new(inst), width, threads)
+}
+
+pub fn solve_problem(pb: &TSPTW, width: Option<usize>, threads: Option<usize>) -> f32 {
+   solve_and_evaluate_problem(pb, width, threads).0
+}
+
+/// Solves the instance and evaluates the best tour with the solution checker
+pub fn solve_and_evaluate(instance: &str, width: Option<usize>, threads: Option<usize>) -> (f32, Option<Evaluation>) {
+   solve_and_evaluate_problem(&TSPTW::new(read(instance)), width, threads)
+}
+
+pub fn solve_and_evaluate_problem(pb: &TSPTW, width: Option<usize>, threads: Option<usize>) -> (f32, Option<Evaluation>) {
+   solve_and_evaluate_relaxed(pb, TSPTWRelax::new(pb), width, threads)
+}
+
+pub fn solve_relaxed<'a>(pb: &'a TSPTW, relax: TSPTWRelax<'a>, width: Option<usize>, threads: Option<usize>) -> f32 {
+   solve_and_evaluate_relaxed(pb, relax, width, threads).0
+}
+
+pub fn solve_and_evaluate_relaxed<'a>(pb: &'a TSPTW, relax: TSPTWRelax<'a>, width: Option<usize>, threads: Option<usize>) -> (f32, Option<Evaluation>) {
+   let mut solver = mk_solver(pb, relax, width, threads);
    let outcome=solver.as_mut().maximize();
-   outcome.best_value.map(|v| -(v as f32) / 10000.0).unwrap_or(-1.0)
+   let value      = outcome.best_value.map(|v| -pb.instance.to_decimal(v) as f32).unwrap_or(-1.0);
+   let eval       = solver.as_ref().best_solution().map(|s| {
+       let tour = Tour::from_solution(pb, &s).expect("invalid tour");
+       evaluate(&pb.instance, &tour)
+   });
+   (value, eval)
 }
 
+/// A tiny deterministic pseudo random generator
+pub struct Lcg(pub u64);
+impl Lcg {
+    pub fn next(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % bound
+    }
+}
+
+/// The distances between n random points of a 20x20 grid. These satisfy the 
+/// triangle inequality (hence manhattan)
+pub fn random_distances(rng: &mut Lcg, n: usize) -> Matrix<usize> {
+    let coords        = (0..n).map(|_| (rng.next(20) as isize, rng.next(20) as isize)).collect::<Vec<_>>();
+    let mut distances = Matrix::new_default(n, n, 0);
+    for i in 0..n {
+        for j in 0..n {
+            distances[(i, j)] = ((coords[i].0 - coords[j].0).abs() + (coords[i].1 - coords[j].1).abs()) as usize;
+        }
+    }
+    distances
+}
+
+/// Every ordering of the nodes of `todo`
+pub fn permutations(todo: &mut Vec<usize>, done: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+    if todo.is_empty() {
+        out.push(done.clone());
+    }
+    for k in 0..todo.len() {
+        let node = todo.remove(k);
+        done.push(node);
+        permutations(todo, done, out);
+        done.pop();
+        todo.insert(k, node);
+    }
+}
+
+/// Every sequence of distinct nodes taken from `todo`
+pub fn arrangements(todo: &mut Vec<usize>, done: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+    out.push(done.clone());
+    for k in 0..todo.len() {
+        let node = todo.remove(k);
+        done.push(node);
+        arrangements(todo, done, out);
+        done.pop();
+        todo.insert(k, node);
+    }
+}
+
+/// All the objectives the solver can minimize
+pub const OBJECTIVES: [Objective; 4] = [Objective::TravelTime, Objective::Makespan, Objective::TravelAndWaiting, Objective::Cumulative];
+
+/// Solves `count` instances of the generator for each of the given objectives
+/// and bounds, and checks that the optimum is the value of the best tour found
+/// by brute force. The tours are enumerated from the sequences of distinct
+/// customers (optional customers may be left out) in which the routes of the
+/// vehicles may be split anywhere.
+pub fn assert_optimal_on<F>(mut random_instance: F, count: usize, objectives: &[Objective], bounds: &[Bound])
+    where F: FnMut() -> TSPTWInstance
+{
+    for k in 0..count {
+        let inst  = random_instance();
+        let evals = sequences(&inst).iter()
+            .filter_map(|seq| Tour::from_sequence(&inst, seq).ok())
+            .map(|tour| evaluate(&inst, &tour))
+            .filter(|e| e.is_feasible())
+            .collect::<Vec<_>>();
+
+        for objective in objectives.iter() {
+            let best = evals.iter()
+                .map(|e| {
+                    let service = e.tour.route().iter().filter(|i| inst.is_customer(**i)).map(|i| inst.service_times[*i]).sum::<usize>();
+                    e.penalty + e.forfeited + match objective {
+                        Objective::TravelTime       => e.tour_cost,
+                        Objective::Makespan         => e.makespan,
+                        Objective::TravelAndWaiting => e.makespan - service,
+                        Objective::Cumulative       => e.latency,
+                    }
+                })
+                .min()
+                .map_or(-1.0, |v| v as f32);
+            let pb = TSPTW::new(inst.clone()).with_objective(*objective);
+            for bound in bounds.iter() {
+                let relax = TSPTWRelax::new(&pb).with_bound(*bound);
+                assert_eq!(best, solve_relaxed(&pb, relax, Some(1), Some(1)), "instance #{} {:?} {:?}", k, objective, bound);
+            }
+        }
+    }
+}
+/// The candidate sequences of the brute force: every arrangement of the 
+/// customers in which a depot is inserted anywhere for each extra vehicle
+fn sequences(inst: &TSPTWInstance) -> Vec<Vec<usize>> {
+    let mut out = vec![];
+    arrangements(&mut inst.customers().collect(), &mut vec![], &mut out);
+    for _ in 1..inst.fleet {
+        out = out.iter()
+            .flat_map(|seq| (0..=seq.len()).map(move |k| {
+                let mut seq = seq.clone();
+                seq.insert(k, 0);
+                seq
+            }))
+            .collect();
+    }
+    out
+}
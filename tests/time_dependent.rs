@@ -0,0 +1,176 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use ddo::Matrix;
+use tsptw::{check::evaluate, instance::{Lateness, ParseError, TSPTWInstance, TimeWindow, TravelFunction}, model::{Objective, TSPTW}, relax::Bound, schedule::schedule, tour::Tour};
+
+mod test_utils;
+use test_utils::{assert_optimal_on, random_distances, Lcg, OBJECTIVES};
+
+// The three nodes are 10 apart from one another. The road from customer 2
+// back to the depot is jammed when the salesman leaves after time 15: both
+// tours travel the same distance but the tour through customer 1 last avoids
+// the rush hour.
+const INSTANCE: &str = "3
+0  10 10
+10 0  10
+10 10 0
+0 200
+0 200
+0 200
+TRAVEL_TIMES
+2 0 0 10 15 10 20 30
+";
+
+fn parse(text: &str) -> Result<TSPTWInstance, ParseError> {
+    TSPTWInstance::try_from(Cursor::new(text).lines())
+}
+
+#[test]
+fn travel_times_are_parsed() {
+    let inst = parse(INSTANCE).unwrap();
+    assert!(inst.has_travel_functions());
+    assert_eq!(&[(0, 10), (15, 10), (20, 30)], inst.travel_times[(2, 0)].as_ref().unwrap().breakpoints());
+    assert_eq!(None, inst.travel_times[(0, 2)]);
+
+    assert_eq!(10, inst.travel_time(0, 2, 17));
+    assert_eq!(10, inst.travel_time(2, 0, 5));
+    assert_eq!(18, inst.travel_time(2, 0, 17));
+    assert_eq!(30, inst.travel_time(2, 0, 90));
+    assert_eq!(18, inst.min_travel_time(2, 0, 17, 30));
+    assert_eq!(10, inst.min_travel_time(2, 0, 5, 30));
+
+    let inst = parse(&INSTANCE.replace("20 30\n", "20 30.5\n")).unwrap();
+    assert_eq!(100, inst.distances[(2, 0)]);
+    assert_eq!(&[(0, 100), (150, 100), (200, 305)], inst.travel_times[(2, 0)].as_ref().unwrap().breakpoints());
+
+    let inst = parse(&INSTANCE.replace("TRAVEL_TIMES\n2 0 0 10 15 10 20 30\n", "")).unwrap();
+    assert!(!inst.has_travel_functions());
+}
+
+#[test]
+fn invalid_travel_times_are_rejected() {
+    // A departure without travel time
+    match parse(&INSTANCE.replace("20 30\n", "20\n")) {
+        Err(ParseError::BadTravelTimes{line: 9, from: 2, to: 0}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    // Leaving at 15 means arriving after those who leave at 20
+    match parse(&INSTANCE.replace("15 10 20 30\n", "15 40 20 30\n")) {
+        Err(ParseError::BadTravelTimes{line: 9, from: 2, to: 0}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    // Shorter than the distance
+    match parse(&INSTANCE.replace("0 10 15", "0 5 15")) {
+        Err(ParseError::BadTravelTimes{line: 9, from: 2, to: 0}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("2 0 0 10", "2 2 0 10")) {
+        Err(ParseError::BadTravelTimes{line: 9, from: 2, to: 2}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("2 0 0 10", "2 5 0 10")) {
+        Err(ParseError::UnknownNode{line: 9, node: 5}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("2 0 0 10 15 10 20 30\n", "2\n")) {
+        Err(ParseError::WrongRowLength{line: 9, expected: 4, found: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn travel_functions_are_fifo() {
+    assert!(TravelFunction::new(vec![]).is_none());
+    assert!(TravelFunction::new(vec![(10, 5), (10, 6)]).is_none());
+    assert!(TravelFunction::new(vec![(0, 20), (10, 9)]).is_none());
+
+    // The travel time may drop as fast as time goes by
+    let f = TravelFunction::new(vec![(0, 20), (10, 10), (20, 15)]).unwrap();
+    assert_eq!(20, f.at(0));
+    assert_eq!(17, f.at(3));
+    assert_eq!(12, f.at(15));
+    assert_eq!(15, f.at(25));
+    assert_eq!(10, f.min_between(5, 15));
+    assert_eq!(14, f.min_between(0, 6));
+    assert_eq!(10, f.shortest());
+    assert!((0..30).all(|t| t + f.at(t) <= t + 1 + f.at(t + 1)));
+}
+
+#[test]
+fn the_schedule_follows_the_traffic() {
+    let inst  = parse(INSTANCE).unwrap();
+    let stops = schedule(&inst, &[0, 1, 2, 0]);
+    assert_eq!(vec![0, 10, 20, 50], stops.iter().map(|s| s.arrival).collect::<Vec<_>>());
+
+    let eval = evaluate(&inst, &Tour::from_permutation(3, &[1, 2]).unwrap());
+    assert_eq!(50, eval.tour_cost);
+    assert_eq!(50, eval.makespan);
+
+    let eval = evaluate(&inst, &Tour::from_permutation(3, &[2, 1]).unwrap());
+    assert_eq!(30, eval.tour_cost);
+    assert_eq!(30, eval.makespan);
+}
+
+#[test]
+fn the_solver_avoids_the_rush_hour() {
+    for objective in [Objective::TravelTime, Objective::Makespan].iter() {
+        let pb = TSPTW::new(parse(INSTANCE).unwrap()).with_objective(*objective);
+        let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+        assert_eq!(30.0, value);
+        assert_eq!(&[0, 2, 1, 0], eval.unwrap().tour.route());
+    }
+
+    // Customer 1 cannot be served before time 25: the tour must go through
+    // the jam
+    let inst = parse(&INSTANCE.replacen("0 200\n0 200", "0 200\n0 15", 1)).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::TravelTime);
+    assert_eq!(50.0, test_utils::solve_problem(&pb, Some(1), Some(1)));
+}
+
+fn random_instance(rng: &mut Lcg, n: usize) -> TSPTWInstance {
+    let distances = random_distances(rng, n);
+    // About one arc out of three is jammed at some times of the day
+    let mut travel_times = Matrix::new_default(n, n, None);
+    for i in 0..n {
+        for j in (0..n).filter(|j| *j != i) {
+            if rng.next(3) != 0 {
+                continue;
+            }
+            let dist            = distances[(i, j)];
+            let mut breakpoints = vec![(rng.next(20), dist + rng.next(20))];
+            for _ in 0..rng.next(3) {
+                let (d, t)    = *breakpoints.last().unwrap();
+                let departure = d + 1 + rng.next(30);
+                let travel    = dist.max((d + t).saturating_sub(departure)) + rng.next(20);
+                breakpoints.push((departure, travel));
+            }
+            travel_times[(i, j)] = TravelFunction::new(breakpoints);
+        }
+    }
+    let timewindows = (0..n)
+        .map(|i| if i == 0 {
+            TimeWindow::new(0, 150 + rng.next(100))
+        } else {
+            let earliest = rng.next(80);
+            TimeWindow::new(earliest, earliest + 10 + rng.next(50))
+        })
+        .collect();
+    let lateness = (0..n)
+        .map(|_| match rng.next(6) {
+            0 => Some(Lateness { penalty: 1 + rng.next(3), max: None }),
+            _ => None,
+        })
+        .collect();
+    TSPTWInstance {
+        travel_times,
+        lateness,
+        service_times: (0..n).map(|i| if i == 0 { 0 } else { rng.next(5) }).collect(),
+        ..TSPTWInstance::new(distances, timewindows)
+    }
+}
+
+#[test]
+fn the_optimum_is_found_on_random_instances() {
+    let mut rng = Lcg(19);
+    assert_optimal_on(|| random_instance(&mut rng, 7), 50, &OBJECTIVES, &[Bound::CheapestEdge, Bound::SpanningTree]);
+}
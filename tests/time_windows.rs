@@ -0,0 +1,98 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{instance::{ParseError, TSPTWInstance, TimeWindow}, model::{Objective, TSPTW}, relax::Bound, schedule::schedule};
+
+mod test_utils;
+use test_utils::{assert_optimal_on, random_distances, Lcg, OBJECTIVES};
+
+// Node 1 is open in the morning (0-10) and in the afternoon (50-60)
+const INSTANCE: &str = "3
+0  5  10
+5  0  10
+10 10 0
+0 100
+0 10
+0 10
+TIME_WINDOWS
+1 50 60 0 10
+";
+
+fn parse(text: &str) -> Result<TSPTWInstance, ParseError> {
+    TSPTWInstance::try_from(Cursor::new(text).lines())
+}
+fn bounds(windows: &[TimeWindow]) -> Vec<(usize, usize)> {
+    windows.iter().map(|tw| (tw.earliest, tw.latest)).collect()
+}
+
+#[test]
+fn several_windows_can_be_given_to_a_node() {
+    let inst = parse(INSTANCE).unwrap();
+    assert_eq!(vec![(0, 10), (50, 60)], bounds(&inst.slots[1]));
+    assert_eq!(vec![(0, 60)], bounds(&inst.timewindows[1..2]));
+    assert!(inst.slots[0].is_empty());
+    assert!(inst.slots[2].is_empty());
+}
+
+#[test]
+fn overlapping_windows_are_rejected() {
+    match parse(&INSTANCE.replace("1 50 60 0 10", "1 0 10 5 60")) {
+        Err(ParseError::BadTimeWindows{line: 9, node: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("1 50 60 0 10", "1 0 10 50")) {
+        Err(ParseError::BadTimeWindows{line: 9, node: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn service_starts_at_the_next_opening() {
+    let inst = parse(INSTANCE).unwrap();
+    assert_eq!(Some(5),  inst.next_opening(1, 5));
+    assert_eq!(Some(50), inst.next_opening(1, 11));
+    assert_eq!(Some(55), inst.next_opening(1, 55));
+    assert_eq!(None,     inst.next_opening(1, 61));
+    assert_eq!(0,        inst.min_waiting(1, 5, 20));
+    assert_eq!(30,       inst.min_waiting(1, 15, 20));
+
+    let stops = schedule(&inst, &[0, 2, 1, 0]);
+    assert_eq!(20, stops[2].arrival);
+    assert_eq!(50, stops[2].start);
+    assert_eq!(55, stops[3].start);
+}
+
+#[test]
+fn the_solver_waits_for_the_next_window() {
+    let inst = parse(INSTANCE).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::Makespan);
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+    // Node 2 cannot be reached in time after node 1. Hence, the salesman
+    // must visit 2 first and then wait for the afternoon window of 1.
+    assert_eq!(55.0, value);
+    assert_eq!(vec![2, 1], eval.unwrap().tour.customers());
+}
+
+fn random_instance(rng: &mut Lcg, n: usize) -> TSPTWInstance {
+    let distances = random_distances(rng, n);
+    let mut inst  = TSPTWInstance {
+        service_times: (0..n).map(|i| if i == 0 { 0 } else { rng.next(5) }).collect(),
+        ..TSPTWInstance::new(distances, vec![TimeWindow::new(0, 400); n])
+    };
+    for node in 1..n {
+        let mut windows = vec![];
+        let mut time    = rng.next(30);
+        for _ in 0..1 + rng.next(3) {
+            let width = 5 + rng.next(20);
+            windows.push(TimeWindow::new(time, time + width));
+            time += width + 10 + rng.next(40);
+        }
+        assert!(inst.set_slots(node, windows));
+    }
+    inst
+}
+
+#[test]
+fn the_optimum_is_found_on_random_instances() {
+    let mut rng = Lcg(42);
+    assert_optimal_on(|| random_instance(&mut rng, 7), 100, &OBJECTIVES, &[Bound::CheapestEdge]);
+}
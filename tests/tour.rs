@@ -0,0 +1,63 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}, sync::Arc};
+
+use ddo::{Decision, PartialAssignment, Solution};
+use tsptw::{instance::TSPTWInstance, model::TSPTW, tour::{Tour, TourError}};
+
+fn problem() -> TSPTW {
+    let text = "4\n0 1 1 1\n1 0 1 1\n1 1 0 1\n1 1 1 0\n0 9\n0 9\n0 9\n0 9\n";
+    TSPTW::new(TSPTWInstance::try_from(Cursor::new(text).lines()).unwrap())
+}
+fn solution(decisions: &[(usize, isize)]) -> Solution {
+    let fragment = decisions.iter()
+        .map(|(depth, value)| Decision{variable: TSPTW::variable_at(*depth), value: *value})
+        .collect();
+    Solution::new(Arc::new(PartialAssignment::FragmentExtension{
+        parent: Arc::new(PartialAssignment::Empty), fragment}))
+}
+
+#[test]
+fn tour_is_rebuilt_from_depths_not_from_decision_order() {
+    let sol  = solution(&[(3, 0), (0, 2), (2, 1), (1, 3)]);
+    let tour = Tour::from_solution(&problem(), &sol).unwrap();
+    assert_eq!(&[0, 2, 3, 1, 0], tour.route());
+    assert_eq!(&[2, 3, 1], tour.customers());
+    assert_eq!(" 2 3 1 0", tour.to_string());
+}
+
+#[test]
+fn incomplete_or_inconsistent_solutions_are_rejected() {
+    let pb = problem();
+    assert_eq!(Err(TourError::MissingDecision(2)), 
+               Tour::from_solution(&pb, &solution(&[(0, 2), (1, 3), (3, 0)])));
+    assert_eq!(Err(TourError::DuplicateDecision(1)), 
+               Tour::from_solution(&pb, &solution(&[(0, 2), (1, 3), (1, 1), (3, 0)])));
+    assert_eq!(Err(TourError::NotBackToDepot), 
+               Tour::from_solution(&pb, &solution(&[(0, 2), (1, 3), (2, 0), (3, 1)])));
+    assert_eq!(Err(TourError::DuplicateNode(3)), 
+               Tour::from_solution(&pb, &solution(&[(0, 3), (1, 3), (2, 1), (3, 0)])));
+}
+
+#[test]
+fn permutations_may_mention_the_depot_at_their_ends() {
+    let expected = Tour::from_permutation(4, &[1, 2, 3]).unwrap();
+    assert_eq!(expected, Tour::from_permutation(4, &[1, 2, 3, 0]).unwrap());
+    assert_eq!(expected, Tour::from_permutation(4, &[0, 1, 2, 3, 0]).unwrap());
+}
+
+#[test]
+fn invalid_permutations_are_rejected() {
+    assert_eq!(Err(TourError::WrongLength{expected: 3, found: 2}), Tour::from_permutation(4, &[1, 2]));
+    assert_eq!(Err(TourError::DuplicateNode(2)), Tour::from_permutation(4, &[1, 2, 2]));
+    assert_eq!(Err(TourError::UnknownNode(4)),   Tour::from_permutation(4, &[1, 2, 4]));
+    assert_eq!(Err(TourError::DepotInPermutation), Tour::from_permutation(4, &[1, 0, 2]));
+}
+
+#[test]
+fn decisions_must_be_allowed_by_the_model() {
+    // Customer 1 must be visited before customer 2
+    let text = "4\n0 1 1 1\n1 0 1 1\n1 1 0 1\n1 1 1 0\n0 9\n0 9\n0 9\n0 9\nPRECEDENCES\n1 2\n";
+    let pb   = TSPTW::new(TSPTWInstance::try_from(Cursor::new(text).lines()).unwrap());
+    assert!(Tour::from_solution(&pb, &solution(&[(0, 1), (1, 3), (2, 2), (3, 0)])).is_ok());
+    assert_eq!(Err(TourError::ForbiddenDecision(0)),
+               Tour::from_solution(&pb, &solution(&[(0, 2), (1, 3), (2, 1), (3, 0)])));
+}
@@ -0,0 +1,158 @@
+use std::{convert::TryFrom, io::{BufRead, Cursor}};
+
+use tsptw::{check::{check, evaluate}, instance::{ParseError, TSPTWInstance, TimeWindow}, model::{Objective, TSPTW}, relax::Bound, tour::{Tour, TourError}};
+
+mod test_utils;
+use test_utils::{assert_optimal_on, random_distances, Lcg, OBJECTIVES};
+
+// Customers 1 and 2 lie on both sides of the depot and must both be served
+// at time 10: a single vehicle cannot serve them both.
+const INSTANCE: &str = "3
+0  10 10
+10 0  20
+10 20 0
+0 100
+10 10
+10 10
+VEHICLES 2
+";
+
+fn parse(text: &str) -> Result<TSPTWInstance, ParseError> {
+    TSPTWInstance::try_from(Cursor::new(text).lines())
+}
+fn bounds(tw: TimeWindow) -> (usize, usize) {
+    (tw.earliest, tw.latest)
+}
+
+#[test]
+fn fleet_and_shifts_are_parsed() {
+    let inst = parse(INSTANCE).unwrap();
+    assert_eq!(2, inst.fleet);
+    assert!(inst.shifts.is_empty());
+    assert_eq!((0, 100), bounds(inst.shift(1)));
+
+    let inst = parse(&INSTANCE.replace("VEHICLES 2\n", "VEHICLES 3\nSHIFTS\n0 50\n20.5 100\n")).unwrap();
+    assert_eq!(3, inst.fleet);
+    assert_eq!(vec![(0, 500), (205, 1000)], inst.shifts.iter().copied().map(bounds).collect::<Vec<_>>());
+    assert_eq!((0, 1000), bounds(inst.shift(2)));
+
+    let inst = parse(&INSTANCE.replace("VEHICLES 2\n", "SHIFTS\n0 50\n20 100\n")).unwrap();
+    assert_eq!(2, inst.fleet);
+
+    let inst = parse(&INSTANCE.replace("VEHICLES 2\n", "")).unwrap();
+    assert_eq!(1, inst.fleet);
+}
+
+#[test]
+fn the_fleet_may_be_changed_as_long_as_it_covers_the_shifts() {
+    let mut inst = parse(&INSTANCE.replace("VEHICLES 2\n", "SHIFTS\n0 50\n20 100\n")).unwrap();
+    assert!(inst.set_fleet(3).is_ok());
+    assert_eq!(3, inst.fleet);
+    for fleet in [0, 1].iter().copied() {
+        match inst.set_fleet(fleet) {
+            Err(ParseError::IncompatibleFleet{fleet: f}) => assert_eq!(fleet, f),
+            other => panic!("unexpected outcome {:?}", other.err()),
+        }
+    }
+    assert_eq!(3, inst.fleet);
+}
+
+#[test]
+fn invalid_fleets_are_rejected() {
+    match parse(&INSTANCE.replace("VEHICLES 2\n", "VEHICLES 0\n")) {
+        Err(ParseError::BadFleet{line: 8, ..}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("VEHICLES 2\n", "VEHICLES 1\nSHIFTS\n0 50\n20 100\n")) {
+        Err(ParseError::TooManyShifts{line: 11, fleet: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+    match parse(&INSTANCE.replace("VEHICLES 2\n", "SHIFTS\n0\n")) {
+        Err(ParseError::WrongRowLength{line: 9, expected: 2, found: 1}) => (),
+        other => panic!("unexpected outcome {:?}", other.err()),
+    }
+}
+
+#[test]
+fn a_tour_is_split_into_routes() {
+    let tour = Tour::from_routes(4, 2, &[1, 0, 2, 3]).unwrap();
+    assert_eq!(&[0, 1, 0, 2, 3, 0], tour.route());
+    assert_eq!(vec![&[0, 1, 0][..], &[0, 2, 3, 0][..]], tour.routes());
+    assert_eq!(tour, Tour::from_routes(4, 2, &[1, 0, 2, 3, 0]).unwrap());
+
+    let tour = Tour::from_routes(4, 3, &[0, 1, 2, 3]).unwrap();
+    assert_eq!(vec![&[0, 0][..], &[0, 1, 2, 3, 0][..]], tour.routes());
+
+    assert_eq!(Err(TourError::TooManyRoutes{fleet: 1, found: 2}), Tour::from_routes(4, 1, &[1, 0, 2, 3]));
+    assert_eq!(Err(TourError::WrongLength{expected: 3, found: 2}), Tour::from_routes(4, 2, &[1, 0, 2]));
+}
+
+#[test]
+fn routes_are_evaluated_separately() {
+    let inst = parse(&INSTANCE.replace("VEHICLES 2\n", "SHIFTS\n0 100\n0 30\n")).unwrap();
+    let eval = check(&inst, Cursor::new(" 1 0 2 0").lines()).unwrap();
+    assert!(eval.is_feasible());
+    assert_eq!(40, eval.tour_cost);
+    assert_eq!(20 + 20, eval.makespan);
+    assert_eq!(vec![0, 10, 20, 10, 20], eval.stops.iter().map(|s| s.start).collect::<Vec<_>>());
+
+    let eval = evaluate(&inst, &Tour::from_routes(3, 2, &[0, 1, 2]).unwrap());
+    assert_eq!(vec![2, 0], eval.violations);
+    assert_eq!(40, eval.makespan);
+}
+
+#[test]
+fn the_solver_uses_several_vehicles_when_it_must() {
+    let inst = parse(INSTANCE).unwrap();
+    let pb   = TSPTW::new(inst).with_objective(Objective::TravelTime);
+    let (value, eval) = test_utils::solve_and_evaluate_problem(&pb, Some(1), Some(1));
+    assert_eq!(40.0, value);
+    assert_eq!(2, eval.unwrap().tour.routes().len());
+
+    assert_eq!(-1.0, test_utils::solve_instance(parse(&INSTANCE.replace("VEHICLES 2\n", "")).unwrap(), Some(1), Some(1)));
+}
+
+#[test]
+fn the_shifts_of_the_vehicles_are_enforced() {
+    let text = INSTANCE.replace("VEHICLES 2\n", "SHIFTS\n0 20\n0 20\n");
+    assert_eq!(40.0, test_utils::solve_instance(parse(&text).unwrap(), Some(1), Some(1)));
+
+    let text = INSTANCE.replace("VEHICLES 2\n", "SHIFTS\n0 100\n0 15\n");
+    assert_eq!(-1.0, test_utils::solve_instance(parse(&text).unwrap(), Some(1), Some(1)));
+
+    let text = INSTANCE.replace("VEHICLES 2\n", "SHIFTS\n0 100\n1 100\n");
+    assert_eq!(-1.0, test_utils::solve_instance(parse(&text).unwrap(), Some(1), Some(1)));
+}
+
+fn random_instance(rng: &mut Lcg, n: usize) -> TSPTWInstance {
+    let distances = random_distances(rng, n);
+    // The windows are narrow enough for a single vehicle to fail most of
+    // the time
+    let timewindows = (0..n)
+        .map(|i| if i == 0 {
+            TimeWindow::new(0, 80 + rng.next(60))
+        } else {
+            let earliest = rng.next(50);
+            TimeWindow::new(earliest, earliest + 5 + rng.next(15))
+        })
+        .collect();
+    let shifts = match rng.next(3) {
+        0 => vec![],
+        _ => (0..2).map(|_| { let e = rng.next(20); TimeWindow::new(e, e + 40 + rng.next(80)) }).collect(),
+    };
+    let capacity = if rng.next(2) == 0 { None } else { Some(4 + rng.next(4)) };
+    TSPTWInstance {
+        service_times: (0..n).map(|i| if i == 0 { 0 } else { rng.next(5) }).collect(),
+        loads        : (0..n).map(|i| if i == 0 { 0 } else { rng.next(4) as isize }).collect(),
+        capacity,
+        fleet        : 2,
+        shifts,
+        ..TSPTWInstance::new(distances, timewindows)
+    }
+}
+
+#[test]
+fn the_optimum_is_found_on_random_instances() {
+    let mut rng = Lcg(11);
+    assert_optimal_on(|| random_instance(&mut rng, 6), 100, &OBJECTIVES, &[Bound::CheapestEdge]);
+}